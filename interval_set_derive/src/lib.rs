@@ -0,0 +1,220 @@
+#![deny(missing_docs)]
+
+//! Derive macros for the `Step` and `Bounded` traits of `interval_set`.
+//!
+//! The traits can be derived for newtypes, that delegate to the wrapped type,
+//! and for fieldless enums, that step through the variants in declaration
+//! order.
+//!
+//! The macros are re-exported by `interval_set` when the `derive` feature is
+//! enabled, they should be used through it.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DataEnum, DeriveInput, Error, Fields, Ident, Member, Type};
+
+/// Derives `Step` for a newtype or a fieldless enum
+///
+/// For a struct with a single field the implementation delegates to the
+/// wrapped type, that must implement `Step`.
+///
+/// For a fieldless enum the successor of a variant is the next declared
+/// variant, the ordering of the enum must follow the declaration order, like
+/// the one of `#[derive(Ord)]`.
+#[proc_macro_derive(Step)]
+pub fn derive_step(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_step(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Bounded` for a newtype or a fieldless enum
+///
+/// For a struct with a single field the implementation delegates to the
+/// wrapped type, that must implement `Bounded`.
+///
+/// For a fieldless enum the bounds are the first and last declared variants.
+#[proc_macro_derive(Bounded)]
+pub fn derive_bounded(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_bounded(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Returns the member and the type of the field of a newtype
+fn newtype_field(input: &DeriveInput) -> syn::Result<(Member, &Type)> {
+    let error = || Error::new_spanned(&input.ident, "expected a struct with a single field");
+    let Data::Struct(data) = &input.data else { return Err(error()) };
+
+    let mut fields = data.fields.iter();
+    match (fields.next(), fields.next()) {
+        (Some(field), None) => {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(0.into())
+            };
+            Ok((member, &field.ty))
+        }
+        _ => Err(error())
+    }
+}
+
+/// Returns the variants of a fieldless enum, in declaration order
+fn enum_variants(data: &DataEnum) -> syn::Result<Vec<&Ident>> {
+    if data.variants.is_empty() {
+        return Err(Error::new(Span::call_site(), "expected an enum with at least one variant"));
+    }
+    data.variants.iter()
+        .map(|variant| match variant.fields {
+            Fields::Unit => Ok(&variant.ident),
+            _ => Err(Error::new_spanned(variant, "expected a variant without fields"))
+        })
+        .collect()
+}
+
+fn expand_step(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if let Data::Enum(data) = &input.data {
+        return expand_step_enum(input, data);
+    }
+    let (member, ty) = newtype_field(input)?;
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(parse_quote!(#ty: ::interval_set::Step));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::interval_set::Step for #name #ty_generics #where_clause {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, ::core::option::Option<usize>) {
+                <#ty as ::interval_set::Step>::steps_between(&start.#member, &end.#member)
+            }
+
+            #[inline]
+            fn forward_checked(start: &Self) -> ::core::option::Option<Self> {
+                <#ty as ::interval_set::Step>::forward_checked(&start.#member)
+                    .map(|inner| Self { #member: inner })
+            }
+
+            #[inline]
+            fn backward_checked(start: &Self) -> ::core::option::Option<Self> {
+                <#ty as ::interval_set::Step>::backward_checked(&start.#member)
+                    .map(|inner| Self { #member: inner })
+            }
+
+            #[inline]
+            fn forward_by_checked(start: &Self, count: usize) -> ::core::option::Option<Self> {
+                <#ty as ::interval_set::Step>::forward_by_checked(&start.#member, count)
+                    .map(|inner| Self { #member: inner })
+            }
+
+            #[inline]
+            fn backward_by_checked(start: &Self, count: usize) -> ::core::option::Option<Self> {
+                <#ty as ::interval_set::Step>::backward_by_checked(&start.#member, count)
+                    .map(|inner| Self { #member: inner })
+            }
+        }
+    })
+}
+
+fn expand_step_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let variants = enum_variants(data)?;
+    let indices = 0..variants.len();
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // The index of `value` in the declaration order, and its inverse
+    let index = quote! {
+        fn index #impl_generics (value: &#name #ty_generics) -> usize #where_clause {
+            match value { #( #name::#variants => #indices, )* }
+        }
+    };
+    let indices = 0..variants.len();
+    let variant = quote! {
+        fn variant #impl_generics (index: usize) -> ::core::option::Option<#name #ty_generics> #where_clause {
+            match index {
+                #( #indices => ::core::option::Option::Some(#name::#variants), )*
+                _ => ::core::option::Option::None
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::interval_set::Step for #name #ty_generics #where_clause {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, ::core::option::Option<usize>) {
+                #index
+                let (start, end) = (index(start), index(end));
+                if start <= end {
+                    (end - start, ::core::option::Option::Some(end - start))
+                } else {
+                    (0, ::core::option::Option::None)
+                }
+            }
+
+            #[inline]
+            fn forward_checked(start: &Self) -> ::core::option::Option<Self> {
+                #index
+                #variant
+                variant(index(start) + 1)
+            }
+
+            #[inline]
+            fn backward_checked(start: &Self) -> ::core::option::Option<Self> {
+                #index
+                #variant
+                variant(index(start).checked_sub(1)?)
+            }
+
+            #[inline]
+            fn forward_by_checked(start: &Self, count: usize) -> ::core::option::Option<Self> {
+                #index
+                #variant
+                variant(index(start).checked_add(count)?)
+            }
+
+            #[inline]
+            fn backward_by_checked(start: &Self, count: usize) -> ::core::option::Option<Self> {
+                #index
+                #variant
+                variant(index(start).checked_sub(count)?)
+            }
+        }
+    })
+}
+
+fn expand_bounded(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    if let Data::Enum(data) = &input.data {
+        return expand_bounded_enum(input, data);
+    }
+    let (member, ty) = newtype_field(input)?;
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+    generics.make_where_clause().predicates.push(parse_quote!(#ty: ::interval_set::Bounded));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::interval_set::Bounded for #name #ty_generics #where_clause {
+            const MIN: Self = Self { #member: <#ty as ::interval_set::Bounded>::MIN };
+            const MAX: Self = Self { #member: <#ty as ::interval_set::Bounded>::MAX };
+        }
+    })
+}
+
+fn expand_bounded_enum(input: &DeriveInput, data: &DataEnum) -> syn::Result<TokenStream2> {
+    let variants = enum_variants(data)?;
+    let (first, last) = (variants[0], variants[variants.len() - 1]);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::interval_set::Bounded for #name #ty_generics #where_clause {
+            const MIN: Self = Self::#first;
+            const MAX: Self = Self::#last;
+        }
+    })
+}
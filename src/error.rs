@@ -0,0 +1,23 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// Error returned when an [`Interval`](crate::Interval) can't be built
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IntervalError {
+    /// The lower bound is greater than the upper bound
+    InvertedBounds,
+    /// The range doesn't contain any element
+    EmptyRange,
+}
+
+impl Display for IntervalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvertedBounds => write!(f, "the lower bound is greater than the upper bound"),
+            Self::EmptyRange => write!(f, "the range is empty"),
+        }
+    }
+}
+
+impl Error for IntervalError {}
@@ -0,0 +1,471 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeInclusive, RangeTo, RangeToInclusive
+};
+
+use crate::error::IntervalError;
+use crate::interval::Interval;
+use crate::traits::Step;
+
+/// An interval over the type `Idx` with inclusive, exclusive or unbounded
+/// endpoints
+///
+/// Unlike [`Interval`] the index only needs to be ordered, and constraints
+/// like `x >= 100` can be represented without the index being
+/// [`Bounded`](crate::Bounded).
+///
+/// The index is treated as dense: `(1, 2)` is not empty even for integers,
+/// since there is no notion of successor. Use [`Interval`] for discrete types.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GenericInterval<Idx: Ord> {
+    lo: Bound<Idx>,
+    hi: Bound<Idx>
+}
+
+/// Datatype for storing a set of [`GenericInterval`]s.
+///
+/// Like [`IntervalSet`](crate::IntervalSet) the representation is minimized,
+/// intervals are merged when they overlap or when they touch, like `[1, 2)` and
+/// `[2, 3]`. Intervals such as `[1, 2)` and `(2, 3]` are kept separate since
+/// `2` is not in the set.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct GenericIntervalSet<Idx: Ord> {
+    intervals: Vec<GenericInterval<Idx>>
+}
+
+/// Compares two lower bounds, an unbounded one is the smallest
+fn cmp_lo<Idx: Ord>(a: &Bound<Idx>, b: &Bound<Idx>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => a.cmp(b),
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Less),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Greater),
+    }
+}
+
+/// Compares two upper bounds, an unbounded one is the largest
+fn cmp_hi<Idx: Ord>(a: &Bound<Idx>, b: &Bound<Idx>) -> Ordering {
+    match (a, b) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => a.cmp(b),
+        (Bound::Included(a), Bound::Excluded(b)) => a.cmp(b).then(Ordering::Greater),
+        (Bound::Excluded(a), Bound::Included(b)) => a.cmp(b).then(Ordering::Less),
+    }
+}
+
+/// Checks that the bounds describe a non empty interval
+fn check_bounds<Idx: Ord>(lo: &Bound<Idx>, hi: &Bound<Idx>) -> Result<(), IntervalError> {
+    match (lo, hi) {
+        (Bound::Included(lo), Bound::Included(hi)) if lo > hi => Err(IntervalError::InvertedBounds),
+        (Bound::Included(lo) | Bound::Excluded(lo), Bound::Excluded(hi))
+        | (Bound::Excluded(lo), Bound::Included(hi)) => match lo.cmp(hi) {
+            Ordering::Less => Ok(()),
+            Ordering::Equal => Err(IntervalError::EmptyRange),
+            Ordering::Greater => Err(IntervalError::InvertedBounds),
+        },
+        _ => Ok(())
+    }
+}
+
+/// Checks if there are values between the upper bound `hi` and the following
+/// lower bound `lo`, so the intervals can't be merged
+fn separated<Idx: Ord>(hi: &Bound<Idx>, lo: &Bound<Idx>) -> bool {
+    match (hi, lo) {
+        (Bound::Included(h) | Bound::Excluded(h), Bound::Included(l) | Bound::Excluded(l)) => {
+            match h.cmp(l) {
+                Ordering::Less => true,
+                Ordering::Equal => matches!((hi, lo), (Bound::Excluded(_), Bound::Excluded(_))),
+                Ordering::Greater => false,
+            }
+        }
+        _ => false
+    }
+}
+
+/// Returns the bound that starts right where `bound` ends, or the opposite
+///
+/// Panics:
+/// - If the bound is unbounded
+fn flip<Idx: Clone>(bound: &Bound<Idx>) -> Bound<Idx> {
+    match bound {
+        Bound::Included(x) => Bound::Excluded(x.clone()),
+        Bound::Excluded(x) => Bound::Included(x.clone()),
+        Bound::Unbounded => panic!("An unbounded endpoint can't be flipped"),
+    }
+}
+
+impl<Idx: Clone + Ord> GenericInterval<Idx> {
+    /// Create a new interval
+    ///
+    /// Panics:
+    /// - If the interval is empty, like when the lower bound is greater than
+    ///   the upper bound or for `(1, 1)`
+    pub fn new(lo: Bound<Idx>, hi: Bound<Idx>) -> Self {
+        match Self::try_new(lo, hi) {
+            Ok(interval) => interval,
+            Err(err) => panic!("The bounds don't describe a valid interval: {err}"),
+        }
+    }
+
+    /// Create a new interval, returning an error instead of panicking when the
+    /// bounds are not valid
+    ///
+    /// Errors:
+    /// - [`IntervalError::InvertedBounds`] if the lower bound is greater than
+    ///   the upper bound
+    /// - [`IntervalError::EmptyRange`] if the bounds are equal and one of them
+    ///   is exclusive
+    pub fn try_new(lo: Bound<Idx>, hi: Bound<Idx>) -> Result<Self, IntervalError> {
+        check_bounds(&lo, &hi)?;
+        Ok(Self { lo, hi })
+    }
+
+    /// Get the interval containing all the values of the type
+    pub fn full() -> Self {
+        Self { lo: Bound::Unbounded, hi: Bound::Unbounded }
+    }
+
+    /// Get the lower bound of the interval
+    #[inline]
+    pub fn lo(&self) -> Bound<&Idx> { self.lo.as_ref() }
+
+    /// Get the upper bound of the interval
+    #[inline]
+    pub fn hi(&self) -> Bound<&Idx> { self.hi.as_ref() }
+
+    /// Get the lower and upper bounds of the interval, consuming it
+    #[inline]
+    pub fn into_bounds(self) -> (Bound<Idx>, Bound<Idx>) { (self.lo, self.hi) }
+
+    /// Checks if `value` is in the interval
+    pub fn contains(&self, value: &Idx) -> bool {
+        let above_lo = match &self.lo {
+            Bound::Included(lo) => lo <= value,
+            Bound::Excluded(lo) => lo < value,
+            Bound::Unbounded => true,
+        };
+        let below_hi = match &self.hi {
+            Bound::Included(hi) => value <= hi,
+            Bound::Excluded(hi) => value < hi,
+            Bound::Unbounded => true,
+        };
+        above_lo && below_hi
+    }
+
+    /// Computes the intersection between two intervals, the result is [`None`]
+    /// if the intervals are disjoint
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lo = std::cmp::max_by(&self.lo, &other.lo, |a, b| cmp_lo(a, b));
+        let hi = std::cmp::min_by(&self.hi, &other.hi, |a, b| cmp_hi(a, b));
+        Self::try_new(lo.clone(), hi.clone()).ok()
+    }
+}
+
+impl<Idx> Debug for GenericInterval<Idx>
+    where Idx: Debug + Ord
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.lo {
+            Bound::Included(lo) => write!(f, "[{lo:?}, ")?,
+            Bound::Excluded(lo) => write!(f, "({lo:?}, ")?,
+            Bound::Unbounded => write!(f, "(-inf, ")?,
+        }
+        match &self.hi {
+            Bound::Included(hi) => write!(f, "{hi:?}]"),
+            Bound::Excluded(hi) => write!(f, "{hi:?})"),
+            Bound::Unbounded => write!(f, "+inf)"),
+        }
+    }
+}
+
+impl<Idx> Display for GenericInterval<Idx>
+    where Idx: Display + Ord
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.lo {
+            Bound::Included(lo) => write!(f, "[{lo}, ")?,
+            Bound::Excluded(lo) => write!(f, "({lo}, ")?,
+            Bound::Unbounded => write!(f, "(-inf, ")?,
+        }
+        match &self.hi {
+            Bound::Included(hi) => write!(f, "{hi}]"),
+            Bound::Excluded(hi) => write!(f, "{hi})"),
+            Bound::Unbounded => write!(f, "+inf)"),
+        }
+    }
+}
+
+impl<Idx: Ord> RangeBounds<Idx> for GenericInterval<Idx> {
+    fn start_bound(&self) -> Bound<&Idx> {
+        self.lo.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&Idx> {
+        self.hi.as_ref()
+    }
+}
+
+impl<Idx: Clone + Ord> From<(Bound<Idx>, Bound<Idx>)> for GenericInterval<Idx> {
+    /// Panics:
+    /// - If the interval is empty, see [`GenericInterval::new`]
+    fn from((lo, hi): (Bound<Idx>, Bound<Idx>)) -> Self {
+        Self::new(lo, hi)
+    }
+}
+
+impl<Idx: Clone + Ord> From<Idx> for GenericInterval<Idx> {
+    fn from(value: Idx) -> Self {
+        Self { lo: Bound::Included(value.clone()), hi: Bound::Included(value) }
+    }
+}
+
+impl<Idx: Clone + Ord> From<Range<Idx>> for GenericInterval<Idx> {
+    /// Panics:
+    /// - If the range is empty
+    fn from(value: Range<Idx>) -> Self {
+        Self::new(Bound::Included(value.start), Bound::Excluded(value.end))
+    }
+}
+
+impl<Idx: Clone + Ord> From<RangeInclusive<Idx>> for GenericInterval<Idx> {
+    /// Panics:
+    /// - If the range is empty
+    fn from(value: RangeInclusive<Idx>) -> Self {
+        let (lo, hi) = value.into_inner();
+        Self::new(Bound::Included(lo), Bound::Included(hi))
+    }
+}
+
+impl<Idx: Clone + Ord> From<RangeFrom<Idx>> for GenericInterval<Idx> {
+    fn from(value: RangeFrom<Idx>) -> Self {
+        Self { lo: Bound::Included(value.start), hi: Bound::Unbounded }
+    }
+}
+
+impl<Idx: Clone + Ord> From<RangeTo<Idx>> for GenericInterval<Idx> {
+    fn from(value: RangeTo<Idx>) -> Self {
+        Self { lo: Bound::Unbounded, hi: Bound::Excluded(value.end) }
+    }
+}
+
+impl<Idx: Clone + Ord> From<RangeToInclusive<Idx>> for GenericInterval<Idx> {
+    fn from(value: RangeToInclusive<Idx>) -> Self {
+        Self { lo: Bound::Unbounded, hi: Bound::Included(value.end) }
+    }
+}
+
+impl<Idx: Step> From<Interval<Idx>> for GenericInterval<Idx> {
+    fn from(value: Interval<Idx>) -> Self {
+        let (lo, hi) = value.into_bounds();
+        Self { lo: Bound::Included(lo), hi: Bound::Included(hi) }
+    }
+}
+
+impl<Idx: Clone + Ord> GenericIntervalSet<Idx> {
+    /// Returns the empty interval set
+    pub fn empty() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Returns the set the contains a single interval
+    pub fn interval(interval: impl Into<GenericInterval<Idx>>) -> Self {
+        Self { intervals: vec![ interval.into() ] }
+    }
+
+    /// Returns the set containing all the values of the type
+    pub fn full() -> Self {
+        Self::interval(GenericInterval::full())
+    }
+
+    /// Return the number of intervals contained in the set
+    pub fn intervals(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Checks if the set doesn't contain any element
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Checks if `value` is in the set
+    pub fn contains(&self, value: &Idx) -> bool {
+        let index = self.intervals.partition_point(|interval| match &interval.hi {
+            Bound::Included(hi) => hi < value,
+            Bound::Excluded(hi) => hi <= value,
+            Bound::Unbounded => false,
+        });
+        self.intervals.get(index).is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Inserts an interval in the set
+    ///
+    /// Only the intervals that overlap or touch the inserted one are replaced.
+    pub fn insert(&mut self, interval: impl Into<GenericInterval<Idx>>) {
+        let GenericInterval { mut lo, mut hi } = interval.into();
+        let start = self.intervals.partition_point(|i| separated(&i.hi, &lo));
+        let end = self.intervals.partition_point(|i| !separated(&hi, &i.lo));
+        if start < end {
+            if cmp_lo(&self.intervals[start].lo, &lo).is_lt() {
+                lo = self.intervals[start].lo.clone();
+            }
+            if cmp_hi(&self.intervals[end - 1].hi, &hi).is_gt() {
+                hi = self.intervals[end - 1].hi.clone();
+            }
+        }
+        self.intervals.splice(start..end, [GenericInterval { lo, hi }]);
+    }
+
+    /// Removes an interval from the set
+    pub fn remove(&mut self, interval: impl Into<GenericInterval<Idx>>) {
+        *self = self.difference(&Self::interval(interval));
+    }
+
+    /// Returns the iterator over the intervals of the set
+    pub fn iter(&self) -> std::slice::Iter<'_, GenericInterval<Idx>> {
+        self.intervals.iter()
+    }
+
+    /// Performs the union between two sets
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other.iter()).cloned().collect()
+    }
+
+    /// Performs the intersection between two sets
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (lhs, rhs) = (&self.intervals, &other.intervals);
+        let mut result = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < lhs.len() && j < rhs.len() {
+            if let Some(interval) = lhs[i].intersection(&rhs[j]) {
+                result.push(interval);
+            }
+            if cmp_hi(&lhs[i].hi, &rhs[j].hi).is_lt() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals: result }
+    }
+
+    /// Computes the difference between the two sets
+    ///
+    /// The result is the set containing all elements in `self` but not in
+    /// `other`
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+
+    /// Computes the symmetric difference between the two sets
+    ///
+    /// The result is the set containing the elements that are in only one of
+    /// the two sets
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
+
+    /// Takes the complement of the set, retuning the set that contains the
+    /// elements not in the current set
+    ///
+    /// Unlike [`IntervalSet::complement`](crate::IntervalSet::complement) this
+    /// doesn't require the index to be bounded.
+    pub fn complement(&self) -> Self {
+        let mut result = vec![];
+        let mut next_lo = Some(Bound::Unbounded);
+        for interval in &self.intervals {
+            if let Some(lo) = next_lo.take() && !matches!(interval.lo, Bound::Unbounded) {
+                result.push(GenericInterval { lo, hi: flip(&interval.lo) });
+            }
+            next_lo = match &interval.hi {
+                Bound::Unbounded => None,
+                hi => Some(flip(hi)),
+            };
+        }
+        if let Some(lo) = next_lo {
+            result.push(GenericInterval { lo, hi: Bound::Unbounded });
+        }
+        Self { intervals: result }
+    }
+
+    /// Checks if all the elements of the set are in `other`
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Checks if all the elements of `other` are in the set
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Checks if the sets have no elements in common
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+}
+
+impl<Idx: Clone + Ord> Default for GenericIntervalSet<Idx> {
+    /// Returns the empty interval set
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<Idx, I> FromIterator<I> for GenericIntervalSet<Idx>
+    where Idx: Clone + Ord, I: Into<GenericInterval<Idx>>
+{
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut intervals: Vec<GenericInterval<Idx>> = iter.into_iter().map(Into::into).collect();
+        intervals.sort_by(|a, b| cmp_lo(&a.lo, &b.lo));
+
+        let mut result: Vec<GenericInterval<Idx>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match result.last_mut() {
+                Some(last) if !separated(&last.hi, &interval.lo) => {
+                    if cmp_hi(&interval.hi, &last.hi).is_gt() {
+                        last.hi = interval.hi;
+                    }
+                }
+                _ => result.push(interval),
+            }
+        }
+        Self { intervals: result }
+    }
+}
+
+impl<'a, Idx: Ord> IntoIterator for &'a GenericIntervalSet<Idx> {
+    type Item = &'a GenericInterval<Idx>;
+    type IntoIter = std::slice::Iter<'a, GenericInterval<Idx>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+impl<Idx> Debug for GenericIntervalSet<Idx>
+    where Idx: Debug + Ord
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:?}", self.intervals)
+    }
+}
+
+impl<Idx> Display for GenericIntervalSet<Idx>
+    where Idx: Display + Ord
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "(")?;
+        let mut iter = self.intervals.iter();
+        if let Some(first) = iter.next() {
+            write!(f, "{first}")?;
+            for interval in iter {
+                write!(f, " U {interval}")?;
+            }
+        }
+        write!(f, ")")
+    }
+}
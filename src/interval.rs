@@ -1,18 +1,63 @@
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display};
-use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::{
+    Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive
+};
 
-use crate::traits::{Bounded, Step};
+use crate::error::IntervalError;
+use crate::traits::{BoundedFns, Step};
 
 /// An interval over the type `Idx`.
 ///
 /// The intervals must be finite, with a lower and upper bound.
 /// The lower bound must be less or equal than the upper bound.
-#[derive(Clone)]
+///
+/// Intervals are ordered by their lower bound and then by their upper bound,
+/// like the `(lo, hi)` tuple, so they can be sorted and used as keys in
+/// ordered or hashed collections.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Interval<Idx: Step> {
     lo: Idx,
     hi: Idx
 }
 
+/// Relation between two [`Interval`]s, following Allen's interval algebra
+///
+/// Returned by [`Interval::relation`], the names describe the first interval
+/// with respect to the second. Since the intervals are discrete, two intervals
+/// meet when the upper bound of one is the predecessor of the lower bound of
+/// the other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntervalRelation {
+    /// The first interval ends before the second, with a gap between them
+    Precedes,
+    /// The first interval ends right before the second starts
+    Meets,
+    /// The first interval starts before the second and ends inside it
+    Overlaps,
+    /// The first interval starts before the second and they end together
+    FinishedBy,
+    /// The first interval starts before and ends after the second
+    Contains,
+    /// The intervals start together and the first ends before the second
+    Starts,
+    /// The intervals have the same bounds
+    Equals,
+    /// The intervals start together and the first ends after the second
+    StartedBy,
+    /// The first interval starts after and ends before the second
+    During,
+    /// The first interval starts after the second and they end together
+    Finishes,
+    /// The first interval starts inside the second and ends after it
+    OverlappedBy,
+    /// The first interval starts right after the second ends
+    MetBy,
+    /// The first interval starts after the second, with a gap between them
+    PrecededBy,
+}
+
 impl<Idx: Step> Interval<Idx> {
     /// Create a new interval.
     ///
@@ -26,6 +71,36 @@ impl<Idx: Step> Interval<Idx> {
         Self { lo, hi }
     }
 
+    /// Create a new interval, returning an error instead of panicking when the
+    /// bounds are not valid
+    ///
+    /// Errors:
+    /// - [`IntervalError::InvertedBounds`] if the lower bound is greater than
+    ///   the upper bound
+    pub fn try_new(lo: Idx, hi: Idx) -> Result<Self, IntervalError> {
+        if lo <= hi {
+            Ok(Self { lo, hi })
+        } else {
+            Err(IntervalError::InvertedBounds)
+        }
+    }
+
+    /// Create a new interval from an half-open range
+    ///
+    /// Unlike the [`From`] conversion this doesn't panic when the range is
+    /// empty, like `5..5`.
+    ///
+    /// Errors:
+    /// - [`IntervalError::EmptyRange`] if the range doesn't contain any element
+    pub fn try_from_range(range: Range<Idx>) -> Result<Self, IntervalError> {
+        if range.start < range.end {
+            let hi = Idx::backward(&range.end);
+            Ok(Self { lo: range.start, hi })
+        } else {
+            Err(IntervalError::EmptyRange)
+        }
+    }
+
     /// Get the lower bound of the interval
     #[inline]
     pub fn lo(&self) -> &Idx { &self.lo }
@@ -34,6 +109,37 @@ impl<Idx: Step> Interval<Idx> {
     #[inline]
     pub fn hi(&self) -> &Idx { &self.hi }
 
+    /// Get the lower and upper bounds of the interval, consuming it
+    #[inline]
+    pub fn into_bounds(self) -> (Idx, Idx) { (self.lo, self.hi) }
+
+    /// Returns the iterator over all the elements of the interval, in
+    /// ascending order
+    pub fn iter(&self) -> IntervalIter<Idx> {
+        self.clone().into_iter()
+    }
+
+    /// Returns the iterator over consecutive sub-intervals of at most `size`
+    /// elements, covering the whole interval
+    ///
+    /// Panics:
+    /// - If `size` is zero
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = Interval<Idx>> {
+        assert!(size > 0, "The chunk size must be greater than zero");
+        let mut next = Some(self.lo.clone());
+        std::iter::from_fn(move || {
+            let lo = next.take()?;
+            let hi = match Idx::forward_by_checked(&lo, size - 1) {
+                Some(end) if end < self.hi => end,
+                _ => self.hi.clone()
+            };
+            if hi < self.hi {
+                next = Some(Idx::forward(&hi));
+            }
+            Some(Self::new(lo, hi))
+        })
+    }
+
     /// Returns a lower bound for the number of elements in the interval
     ///
     /// The returned value can be lower than the real number of elements,
@@ -55,6 +161,17 @@ impl<Idx: Step> Interval<Idx> {
             .and_then(|s| s.checked_add(1))
     }
 
+    /// Returns the element in the middle of the interval, for intervals with
+    /// an even number of elements the lower one is returned
+    ///
+    /// The result is [`None`] when the number of steps between the bounds
+    /// overflows `usize`, like for the full `u128` interval, since [`Step`]
+    /// can't move by more than `usize::MAX` steps at once.
+    pub fn midpoint(&self) -> Option<Idx> {
+        let steps = Idx::steps_between(&self.lo, &self.hi).1?;
+        Idx::forward_by_checked(&self.lo, steps / 2)
+    }
+
     /// Computes the hull between of the intervals
     ///
     /// The hull is the interval that contains both intervals.
@@ -67,6 +184,14 @@ impl<Idx: Step> Interval<Idx> {
         }
     }
 
+    /// Computes the union of the intervals when it is a single interval
+    ///
+    /// The result is [`None`] when the intervals don't overlap or touch,
+    /// unlike [`Interval::hull`] that also covers the gap between them.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| self.hull(other))
+    }
+
     /// Computes the intersection of the intervals
     ///
     /// The intersection is the interval contained in both intervals.
@@ -93,7 +218,7 @@ impl<Idx: Step> Interval<Idx> {
     /// right interval contains values *bigger* than the subtrahend.
     ///
     /// Example:
-    /// ```
+    /// ```text
     /// a..................A
     ///       b......B
     /// --------------------
@@ -120,23 +245,187 @@ impl<Idx: Step> Interval<Idx> {
         (left, right)
     }
 
+    /// Splits the interval at `at`
+    ///
+    /// The left part contains the values smaller than `at`, while the right
+    /// part contains the values greater or equal than `at`. The resulting
+    /// values are `None` when the part would be empty.
+    pub fn split_at(&self, at: &Idx) -> (Option<Self>, Option<Self>) {
+        let left = if &self.lo < at {
+            let hi = self.hi.clone().min(Idx::backward(at));
+            Some(Self::new(self.lo.clone(), hi))
+        } else {
+            None
+        };
+
+        let right = if at <= &self.hi {
+            let lo = self.lo.clone().max(at.clone());
+            Some(Self::new(lo, self.hi.clone()))
+        } else {
+            None
+        };
+
+        (left, right)
+    }
+
+    /// Computes the relation between the intervals
+    ///
+    /// See [`IntervalRelation`] for the possible relations.
+    pub fn relation(&self, other: &Self) -> IntervalRelation {
+        if self.hi < other.lo {
+            return if Idx::forward_checked(&self.hi).as_ref() == Some(&other.lo) {
+                IntervalRelation::Meets
+            } else {
+                IntervalRelation::Precedes
+            };
+        }
+        if other.hi < self.lo {
+            return if Idx::forward_checked(&other.hi).as_ref() == Some(&self.lo) {
+                IntervalRelation::MetBy
+            } else {
+                IntervalRelation::PrecededBy
+            };
+        }
+
+        match (self.lo.cmp(&other.lo), self.hi.cmp(&other.hi)) {
+            (Ordering::Less, Ordering::Less) => IntervalRelation::Overlaps,
+            (Ordering::Less, Ordering::Equal) => IntervalRelation::FinishedBy,
+            (Ordering::Less, Ordering::Greater) => IntervalRelation::Contains,
+            (Ordering::Equal, Ordering::Less) => IntervalRelation::Starts,
+            (Ordering::Equal, Ordering::Equal) => IntervalRelation::Equals,
+            (Ordering::Equal, Ordering::Greater) => IntervalRelation::StartedBy,
+            (Ordering::Greater, Ordering::Less) => IntervalRelation::During,
+            (Ordering::Greater, Ordering::Equal) => IntervalRelation::Finishes,
+            (Ordering::Greater, Ordering::Greater) => IntervalRelation::OverlappedBy,
+        }
+    }
+
     /// Checks if the interval overlaps another interval
     pub fn overlaps(&self, other: &Self) -> bool {
         self.hi >= other.lo && other.hi >= self.lo
     }
+
+    /// Checks if the interval overlaps or is adjacent to another interval
+    ///
+    /// Two intervals are adjacent when the upper bound of one is the
+    /// predecessor of the lower bound of the other, these are the intervals
+    /// that [`IntervalSet`](crate::IntervalSet) merges together.
+    pub fn touches(&self, other: &Self) -> bool {
+        let (first, second) = if self.lo <= other.lo { (self, other) } else { (other, self) };
+        Idx::forward_checked(&first.hi).is_none_or(|after| second.lo <= after)
+    }
+
+    /// Returns the interval of the values strictly between the two intervals
+    ///
+    /// The result is [`None`] when the intervals overlap or touch.
+    pub fn gap_to(&self, other: &Self) -> Option<Self> {
+        if self.touches(other) {
+            return None;
+        }
+        let (first, second) = if self.lo <= other.lo { (self, other) } else { (other, self) };
+        Some(Self::new(Idx::forward(&first.hi), Idx::backward(&second.lo)))
+    }
+
+    /// Narrows the interval by `n` steps on both ends
+    ///
+    /// The result is [`None`] when the interval has less than `2 * n + 1`
+    /// elements, and would become empty.
+    pub fn shrink(&self, n: usize) -> Option<Self> {
+        let lo = Idx::forward_by_checked(&self.lo, n)?;
+        let hi = Idx::backward_by_checked(&self.hi, n)?;
+        Self::try_new(lo, hi).ok()
+    }
+
+    /// Restricts the interval to the given bounds
+    ///
+    /// The result is [`None`] when the interval is outside of the bounds. This
+    /// is named `clamp_to` since `clamp` would be shadowed by [`Ord::clamp`].
+    pub fn clamp_to(&self, bounds: &Self) -> Option<Self> {
+        self.intersection(bounds)
+    }
+
+    /// Checks if `value` is in the interval
+    pub fn contains(&self, value: &Idx) -> bool {
+        &self.lo <= value && value <= &self.hi
+    }
+
+    /// Checks if all the elements of `other` are in the interval
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
 }
 
 impl<Idx> Interval<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     /// Get the interval the spans all possible values of the type
     ///
-    /// The index type must be [`Bounded`] to use this operation
+    /// The index type must be [`BoundedFns`] to use this operation, this is
+    /// the case for all the [`Bounded`](crate::Bounded) types
     pub fn full() -> Self {
-        Self::new(Idx::MIN, Idx::MAX)
+        Self::new(Idx::min_value(), Idx::max_value())
+    }
+
+    /// Widens the interval by `n` steps on both ends
+    ///
+    /// The bounds saturate at the minimum and maximum values of the type.
+    pub fn expand(&self, n: usize) -> Self {
+        let lo = Idx::backward_by_checked(&self.lo, n).unwrap_or_else(Idx::min_value);
+        let hi = Idx::forward_by_checked(&self.hi, n).unwrap_or_else(Idx::max_value);
+        Self::new(lo, hi)
     }
 }
 
+macro_rules! impl_aligned_blocks {
+    [$($t:ty)*] => {$(
+        impl Interval<$t> {
+            /// Returns the iterator over the blocks that cover the interval,
+            /// the blocks have a power of two size and are aligned to it
+            ///
+            /// The blocks are as large as possible, so the interval is covered
+            /// with the fewest blocks.
+            pub fn aligned_blocks(&self) -> impl Iterator<Item = Interval<$t>> + use<> {
+                let hi = self.hi;
+                let mut next = Some(self.lo);
+                std::iter::from_fn(move || {
+                    let lo = next.take()?;
+                    let align = lo.trailing_zeros();
+                    let fit = match (hi - lo).checked_add(1) {
+                        Some(len) => len.ilog2(),
+                        None => <$t>::BITS
+                    };
+                    let end = match <$t>::checked_shl(1, align.min(fit)) {
+                        Some(size) => lo + (size - 1),
+                        None => <$t>::MAX
+                    };
+                    if end < hi {
+                        next = Some(end + 1);
+                    }
+                    Some(Interval::new(lo, end))
+                })
+            }
+        }
+    )*};
+}
+
+impl_aligned_blocks![u8 u16 u32 u64 u128 usize];
+
+macro_rules! impl_aligned_blocks_addr {
+    [$($addr:ty => $bits:ty),*] => {$(
+        impl Interval<$addr> {
+            /// Returns the iterator over the CIDR blocks that cover the
+            /// interval, using the fewest blocks
+            pub fn aligned_blocks(&self) -> impl Iterator<Item = Interval<$addr>> + use<> {
+                Interval::new(self.lo.to_bits(), self.hi.to_bits())
+                    .aligned_blocks()
+                    .map(|block| Interval::new(<$addr>::from_bits(block.lo), <$addr>::from_bits(block.hi)))
+            }
+        }
+    )*};
+}
+
+impl_aligned_blocks_addr![Ipv4Addr => u32, Ipv6Addr => u128];
+
 impl<Idx> Debug for Interval<Idx>
     where Idx: Debug + Step
 {
@@ -159,6 +448,81 @@ impl<Idx> Display for Interval<Idx>
 
 impl<Idx> Copy for Interval<Idx> where Idx: Copy + Step {}
 
+impl<Idx: Step> IntoIterator for Interval<Idx> {
+    type Item = Idx;
+    type IntoIter = IntervalIter<Idx>;
+
+    fn into_iter(self) -> IntervalIter<Idx> {
+        IntervalIter { bounds: Some(self.into_bounds()) }
+    }
+}
+
+impl<Idx: Step> IntoIterator for &Interval<Idx> {
+    type Item = Idx;
+    type IntoIter = IntervalIter<Idx>;
+
+    fn into_iter(self) -> IntervalIter<Idx> {
+        self.iter()
+    }
+}
+
+/// Iterator over the elements of an interval
+///
+/// This struct is created by [`Interval::iter`] or by the [`IntoIterator`]
+/// implementation of [`Interval`]
+#[derive(Clone)]
+pub struct IntervalIter<Idx: Step> {
+    bounds: Option<(Idx, Idx)>
+}
+
+impl<Idx: Step> Iterator for IntervalIter<Idx> {
+    type Item = Idx;
+
+    fn next(&mut self) -> Option<Idx> {
+        let (lo, hi) = self.bounds.as_mut()?;
+        let value = lo.clone();
+        if lo == hi {
+            self.bounds = None;
+        } else {
+            *lo = Idx::forward(lo);
+        }
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.bounds {
+            Some((lo, hi)) => {
+                let (lower, exact) = Idx::steps_between(lo, hi);
+                (lower.saturating_add(1), exact.and_then(|steps| steps.checked_add(1)))
+            }
+            None => (0, Some(0))
+        }
+    }
+}
+
+impl<Idx: Step> DoubleEndedIterator for IntervalIter<Idx> {
+    fn next_back(&mut self) -> Option<Idx> {
+        let (lo, hi) = self.bounds.as_mut()?;
+        let value = hi.clone();
+        if lo == hi {
+            self.bounds = None;
+        } else {
+            *hi = Idx::backward(hi);
+        }
+        Some(value)
+    }
+}
+
+impl<Idx: Step> RangeBounds<Idx> for Interval<Idx> {
+    fn start_bound(&self) -> Bound<&Idx> {
+        Bound::Included(&self.lo)
+    }
+
+    fn end_bound(&self) -> Bound<&Idx> {
+        Bound::Included(&self.hi)
+    }
+}
+
 impl<Idx: Step> From<Idx> for Interval<Idx> {
     fn from(value: Idx) -> Self {
         Self::new(value.clone(), value)
@@ -172,6 +536,9 @@ impl<Idx: Step> From<&Idx> for Interval<Idx> {
 }
 
 impl<Idx: Step> From<Range<Idx>> for Interval<Idx> {
+    /// Panics:
+    /// - If the range is empty, use [`Interval::try_from_range`] to handle
+    ///   empty ranges
     #[inline]
     fn from(value: Range<Idx>) -> Self {
         let hi = Idx::backward(&value.end);
@@ -187,39 +554,69 @@ impl<Idx: Step> From<RangeInclusive<Idx>> for Interval<Idx> {
     }
 }
 
+impl<Idx: Step> From<Interval<Idx>> for RangeInclusive<Idx> {
+    #[inline]
+    fn from(value: Interval<Idx>) -> Self {
+        let (lo, hi) = value.into_bounds();
+        lo..=hi
+    }
+}
+
 impl<Idx> From<RangeTo<Idx>> for Interval<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     #[inline]
     fn from(value: RangeTo<Idx>) -> Self {
         let hi = Idx::backward(&value.end);
-        Self::new(Idx::MIN, hi)
+        Self::new(Idx::min_value(), hi)
     }
 }
 
 impl<Idx> From<RangeToInclusive<Idx>> for Interval<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     #[inline]
     fn from(value: RangeToInclusive<Idx>) -> Self {
-        Self::new(Idx::MIN, value.end)
+        Self::new(Idx::min_value(), value.end)
     }
 }
 
 impl<Idx> From<RangeFrom<Idx>> for Interval<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     #[inline]
     fn from(value: RangeFrom<Idx>) -> Self {
-        Self::new(value.start, Idx::MAX)
+        Self::new(value.start, Idx::max_value())
     }
 }
 
 impl<Idx> From<RangeFull> for Interval<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     #[inline]
     fn from(_: RangeFull) -> Self {
-        Self::new(Idx::MIN, Idx::MAX)
+        Self::new(Idx::min_value(), Idx::max_value())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Idx> serde::Serialize for Interval<Idx>
+    where Idx: serde::Serialize + Step
+{
+    /// Serializes the interval as a `[lo, hi]` pair
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (&self.lo, &self.hi).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Idx> serde::Deserialize<'de> for Interval<Idx>
+    where Idx: serde::Deserialize<'de> + Step
+{
+    /// Deserializes the interval from a `[lo, hi]` pair, failing if the lower
+    /// bound is greater than the upper bound
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (lo, hi) = <(Idx, Idx)>::deserialize(deserializer)?;
+        Self::try_new(lo, hi).map_err(serde::de::Error::custom)
     }
 }
@@ -1,4 +1,5 @@
 #![deny(missing_docs)]
+#![cfg_attr(feature = "nightly-step", feature(step_trait))]
 
 //! Implementaion of a [`IntervalSet`] that allows to store a set of minimized
 //! intervals.
@@ -9,13 +10,70 @@
 //!
 //! Types using this data structure require a notion of successor and
 //! predecessor and so, the trait [`Step`] needs to be implemented.
+//!
+//! For types that are not discrete, or when intervals need exclusive or
+//! unbounded endpoints, [`GenericIntervalSet`] stores [`GenericInterval`]s
+//! whose endpoints are [`Bound`](std::ops::Bound)s.
+//!
+//! Optional features:
+//! - `chrono`: implements [`Step`] and [`Bounded`] for `chrono::NaiveDate`,
+//!   stepping by one day
+//! - `derive`: provides the `Step` and `Bounded` derive macros for newtypes
+//!   wrapping a type that implements the trait, and for fieldless enums
+//! - `ethnum`: implements [`Step`] and [`Bounded`] for the 256-bit integers
+//!   of the `ethnum` crate
+//! - `nightly-step`: provides the `StdStep` adapter, that implements [`Step`]
+//!   for the types implementing the unstable `std::iter::Step`, requires a
+//!   nightly compiler
+//! - `num`: provides the `NumInt` adapter, that implements [`Step`] for the
+//!   integers of the `num-traits` crate
+//! - `serde`: implements `Serialize` and `Deserialize` for [`Interval`], as
+//!   a `[lo, hi]` pair
+//! - `smallvec`: stores up to two intervals of an [`IntervalSet`] inline,
+//!   without a heap allocation
+//! - `time`: implements [`Step`] and [`Bounded`] for `time::Date`, stepping
+//!   by one day
+//! - `uuid`: implements [`Step`] and [`Bounded`] for `uuid::Uuid`, ordered
+//!   like the corresponding `u128`
 
 mod traits;
-pub use traits::{Step, Bounded};
+pub use traits::{Step, Bounded, BoundedFns};
+
+#[cfg(feature = "derive")]
+pub use interval_set_derive::{Step, Bounded};
 
 mod interval;
-pub use interval::Interval;
+pub use interval::{Interval, IntervalIter, IntervalRelation};
+
+mod error;
+pub use error::IntervalError;
+
+mod storage;
 
 mod set;
-pub use set::IntervalSet;
+pub use set::{
+    IntervalSet, InsertOutcome, SetRelation, RankIndex, Elements, IntoElements, Gaps, Segments
+};
+
+mod non_empty;
+pub use non_empty::NonEmptyIntervalSet;
+
+mod generic;
+pub use generic::{GenericInterval, GenericIntervalSet};
+
+mod step_by;
+pub use step_by::StepBy;
+
+mod ticks;
+pub use ticks::{Nanos, Micros, Millis};
+
+#[cfg(feature = "nightly-step")]
+mod std_step;
+#[cfg(feature = "nightly-step")]
+pub use std_step::StdStep;
+
+#[cfg(feature = "num")]
+mod num;
+#[cfg(feature = "num")]
+pub use num::NumInt;
 
@@ -0,0 +1,99 @@
+use std::fmt::{Debug, Display};
+use std::ops::Deref;
+
+use crate::interval::Interval;
+use crate::set::{InsertOutcome, IntervalSet};
+use crate::traits::Step;
+
+/// An [`IntervalSet`] that contains at least one interval.
+///
+/// All the read only operations of [`IntervalSet`] are available through
+/// [`Deref`], while the operations that depend on the set being non empty are
+/// infallible.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmptyIntervalSet<Idx: Step> {
+    set: IntervalSet<Idx>
+}
+
+impl<Idx: Step> NonEmptyIntervalSet<Idx> {
+    /// Returns the set the contains a single interval
+    pub fn interval(interval: impl Into<Interval<Idx>>) -> Self {
+        Self { set: IntervalSet::interval(interval) }
+    }
+
+    /// Returns the smallest element of the set
+    pub fn first(&self) -> &Idx {
+        self.set.first().expect("the set is not empty")
+    }
+
+    /// Returns the largest element of the set
+    pub fn last(&self) -> &Idx {
+        self.set.last().expect("the set is not empty")
+    }
+
+    /// Returns the interval that spans from the smallest to the largest element
+    /// of the set
+    pub fn hull(&self) -> Interval<Idx> {
+        self.set.hull().expect("the set is not empty")
+    }
+
+    /// Inserts an interval in the set
+    ///
+    /// See [`IntervalSet::insert`]
+    pub fn insert(&mut self, interval: impl Into<Interval<Idx>>) -> InsertOutcome<Idx> {
+        self.set.insert(interval)
+    }
+
+    /// Returns a reference to the underlying set
+    pub fn as_set(&self) -> &IntervalSet<Idx> {
+        &self.set
+    }
+
+    /// Returns the underlying set
+    pub fn into_set(self) -> IntervalSet<Idx> {
+        self.set
+    }
+}
+
+impl<Idx: Step> Deref for NonEmptyIntervalSet<Idx> {
+    type Target = IntervalSet<Idx>;
+
+    fn deref(&self) -> &IntervalSet<Idx> {
+        &self.set
+    }
+}
+
+impl<Idx: Step> TryFrom<IntervalSet<Idx>> for NonEmptyIntervalSet<Idx> {
+    type Error = IntervalSet<Idx>;
+
+    /// Wraps a set that is not empty, an empty set is returned as the error
+    fn try_from(set: IntervalSet<Idx>) -> Result<Self, IntervalSet<Idx>> {
+        if set.intervals() > 0 {
+            Ok(Self { set })
+        } else {
+            Err(set)
+        }
+    }
+}
+
+impl<Idx: Step> From<NonEmptyIntervalSet<Idx>> for IntervalSet<Idx> {
+    fn from(value: NonEmptyIntervalSet<Idx>) -> Self {
+        value.set
+    }
+}
+
+impl<Idx> Debug for NonEmptyIntervalSet<Idx>
+    where Idx: Debug + Step
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.set, f)
+    }
+}
+
+impl<Idx> Display for NonEmptyIntervalSet<Idx>
+    where Idx: Display + Step
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Display::fmt(&self.set, f)
+    }
+}
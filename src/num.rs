@@ -0,0 +1,101 @@
+use std::fmt::{Debug, Display};
+
+use num_traits::PrimInt;
+
+use crate::traits::{BoundedFns, Step};
+
+/// Adapter that implements [`Step`] for any integer implementing
+/// [`num_traits::PrimInt`]
+///
+/// A blanket implementation would conflict with the implementations for the
+/// primitive types, so the integer needs to be wrapped.
+///
+/// The bounds of the integer are not available in a const context, so
+/// [`BoundedFns`] is implemented instead of [`Bounded`](crate::Bounded).
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NumInt<T: PrimInt>(pub T);
+
+impl<T: PrimInt> Step for NumInt<T> {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        let (start, end) = (start.0, end.0);
+        if start > end {
+            return (0, None);
+        }
+        let steps = match end.checked_sub(&start) {
+            Some(diff) => diff.to_usize(),
+            None => {
+                // Only signed types overflow, when `start < 0 <= end`
+                let below = (T::zero() - (start + T::one())).to_usize();
+                let above = end.to_usize();
+                below.zip(above).and_then(|(below, above)| below.checked_add(above)?.checked_add(1))
+            }
+        };
+        match steps {
+            Some(steps) => (steps, Some(steps)),
+            None => (usize::MAX, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        start.0.checked_add(&T::one()).map(Self)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        start.0.checked_sub(&T::one()).map(Self)
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        match T::from(count) {
+            Some(count) => start.0.checked_add(&count).map(Self),
+            None => {
+                // `count` doesn't fit `T` but the result might, like for
+                // signed types, so it is split in two steps
+                let half = count / 2;
+                Self::forward_by_checked(&Self::forward_by_checked(start, half)?, count - half)
+            }
+        }
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        match T::from(count) {
+            Some(count) => start.0.checked_sub(&count).map(Self),
+            None => {
+                let half = count / 2;
+                Self::backward_by_checked(&Self::backward_by_checked(start, half)?, count - half)
+            }
+        }
+    }
+}
+
+impl<T: PrimInt> BoundedFns for NumInt<T> {
+    #[inline]
+    fn min_value() -> Self {
+        Self(T::min_value())
+    }
+
+    #[inline]
+    fn max_value() -> Self {
+        Self(T::max_value())
+    }
+}
+
+impl<T> Debug for NumInt<T>
+    where T: Debug + PrimInt
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T> Display for NumInt<T>
+    where T: Display + PrimInt
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -1,18 +1,68 @@
+use std::borrow::Borrow;
 use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Display};
 use std::iter::Peekable;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound, Not, Range,
+    RangeInclusive, Sub, SubAssign
+};
 
-use crate::interval::Interval;
-use crate::traits::{Bounded, Step};
+use crate::interval::{Interval, IntervalIter};
+use crate::storage::{self, Intervals};
+use crate::traits::{BoundedFns, Step};
 
 /// Datatype for storing a set of intervals.
 ///
 /// The number of intervals are minimized, intervals are merged when possible:
 /// when intervals are overlapping or touching (one end of an interval is
 /// the successor/predecessor of the one end of the other interval)
-#[derive(Clone)]
+///
+/// Since the representation is minimized, two sets are equal exactly when they
+/// contain the same elements.
+///
+/// Sets are ordered lexicographically by their sequence of intervals, this is
+/// a total order unrelated to inclusion, see [`IntervalSet::relation`] to
+/// compare sets by inclusion.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct IntervalSet<Idx: Step> {
-    intervals: Vec<Interval<Idx>>
+    intervals: Intervals<Interval<Idx>>
+}
+
+/// Description of how an insertion changes an [`IntervalSet`]
+///
+/// Returned by [`IntervalSet::insert`] and [`IntervalSet::probe_insert`]
+#[derive(Clone, Debug)]
+pub struct InsertOutcome<Idx: Step> {
+    /// The inserted interval is already fully contained in the set
+    pub covered: bool,
+    /// Number of intervals of the set that overlap or touch the inserted
+    /// interval, and so are merged with it
+    pub merged: usize,
+    /// The interval of the set that contains the inserted interval after the
+    /// insertion
+    pub interval: Interval<Idx>,
+}
+
+/// Relation between two [`IntervalSet`]s
+///
+/// Returned by [`IntervalSet::relation`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetRelation {
+    /// The sets contain the same elements
+    Equal,
+    /// All the elements of the first set are in the second set, that contains
+    /// other elements
+    ProperSubset,
+    /// All the elements of the second set are in the first set, that contains
+    /// other elements
+    ProperSuperset,
+    /// The sets have no elements in common
+    Disjoint,
+    /// The sets have some elements in common, and each has elements that are
+    /// not in the other
+    Overlapping,
 }
 
 /// Macro helper for initializing an [`IntervalSet`]
@@ -29,12 +79,44 @@ macro_rules! iset {
 impl<Idx: Step> IntervalSet<Idx> {
     /// Returns the empty interval set
     pub fn empty() -> Self {
-        Self { intervals: vec![] }
+        Self { intervals: Intervals::new() }
     }
 
     /// Returns the set the contains a single interval
     pub fn interval(interval: impl Into<Interval<Idx>>) -> Self {
-        Self { intervals: vec![ interval.into() ] }
+        Self { intervals: [interval.into()].into_iter().collect() }
+    }
+
+    /// Builds a set from intervals that are already minimized, without any
+    /// validation
+    ///
+    /// The intervals must be sorted, and they must not overlap or touch. This
+    /// is only checked in debug builds, when the requirement is not met the
+    /// operations on the set return wrong results.
+    pub fn from_sorted_unchecked(intervals: Vec<Interval<Idx>>) -> Self {
+        debug_assert!(
+            intervals.windows(2).all(|w| {
+                Idx::forward_checked(w[0].hi()).is_some_and(|after| &after < w[1].lo())
+            }),
+            "The intervals must be sorted, not overlapping and not touching"
+        );
+        Self { intervals: storage::from_vec(intervals) }
+    }
+
+    /// Builds a set from `(start, length)` runs
+    ///
+    /// The runs can be in any order and can overlap, empty runs are ignored.
+    ///
+    /// Panics:
+    /// - If the end of a run overflows the index type
+    pub fn from_runs(runs: impl IntoIterator<Item = (Idx, usize)>) -> Self {
+        runs.into_iter()
+            .filter(|(_, len)| *len > 0)
+            .map(|(start, len)| {
+                let end = Idx::forward_by(&start, len - 1);
+                Interval::new(start, end)
+            })
+            .collect()
     }
 
     /// Return the number of intervals contained in the set
@@ -70,75 +152,497 @@ impl<Idx: Step> IntervalSet<Idx> {
     pub fn size_exact(&self) -> Option<usize> {
         let mut size = 0usize;
         for interval in self.iter() {
-            size = match size.checked_add(interval.size_exact()?) {
-                Some(sum) => sum,
-                None => return None,
-            };
+            size = size.checked_add(interval.size_exact()?)?;
         }
         Some(size)
     }
 
+    /// Returns the smallest element of the set, or [`None`] if it is empty
+    ///
+    /// This is named like [`BTreeSet::first`](std::collections::BTreeSet::first)
+    /// since `min` would be shadowed by [`Ord::min`].
+    pub fn first(&self) -> Option<&Idx> {
+        self.intervals.first().map(Interval::lo)
+    }
+
+    /// Returns the largest element of the set, or [`None`] if it is empty
+    ///
+    /// This is named like [`BTreeSet::last`](std::collections::BTreeSet::last)
+    /// since `max` would be shadowed by [`Ord::max`].
+    pub fn last(&self) -> Option<&Idx> {
+        self.intervals.last().map(Interval::hi)
+    }
+
+    /// Returns the interval that spans from the smallest to the largest element
+    /// of the set, or [`None`] if it is empty
+    pub fn hull(&self) -> Option<Interval<Idx>> {
+        Some(Interval::new(self.first()?.clone(), self.last()?.clone()))
+    }
+
+    /// Returns the `n`-th smallest element of the set, counting from zero
+    ///
+    /// The result is [`None`] if the set has `n` or fewer elements.
+    ///
+    /// This takes linear time in the number of intervals, use
+    /// [`IntervalSet::rank_index`] for repeated queries.
+    pub fn nth(&self, mut n: usize) -> Option<Idx> {
+        for interval in self.iter() {
+            match interval.size_exact() {
+                Some(size) if n >= size => n -= size,
+                _ => return Idx::forward_by_checked(interval.lo(), n)
+            }
+        }
+        None
+    }
+
+    /// Returns the number of elements of the set that are smaller than `value`
+    ///
+    /// The interval containing `value` is found with a binary search, but the
+    /// sizes of the intervals before it are summed on every call, so this takes
+    /// linear time in the number of intervals. Use [`IntervalSet::rank_index`]
+    /// for repeated queries.
+    ///
+    /// Like [`IntervalSet::size`] the count saturates at [`usize::MAX`].
+    pub fn rank(&self, value: &Idx) -> usize {
+        let (index, partial) = match self.search(value) {
+            Ok(index) => (index, Idx::steps_between(self.intervals[index].lo(), value).0),
+            Err(index) => (index, 0)
+        };
+        self.intervals[..index].iter()
+            .map(Interval::size)
+            .fold(partial, usize::saturating_add)
+    }
+
+    /// Builds a [`RankIndex`] that answers [`IntervalSet::rank`] and
+    /// [`IntervalSet::nth`] queries in logarithmic time
+    ///
+    /// The index stores the prefix sums of the interval sizes, it borrows the
+    /// set so it can't get out of date.
+    pub fn rank_index(&self) -> RankIndex<'_, Idx> {
+        let mut before = Vec::with_capacity(self.intervals.len());
+        let mut total = 0usize;
+        for interval in self.iter() {
+            before.push(total);
+            total = total.saturating_add(interval.size());
+        }
+        RankIndex { intervals: &self.intervals, before }
+    }
+
+    /// Returns the number of elements of the set that are in `range`
+    ///
+    /// Like [`IntervalSet::size`] the count saturates at [`usize::MAX`].
+    pub fn count_in(&self, range: impl Into<Interval<Idx>>) -> usize {
+        let range = range.into();
+        self.intervals[self.overlapping_span(&range)].iter()
+            .filter_map(|interval| interval.intersection(&range))
+            .map(|interval| interval.size())
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Returns the smallest value greater or equal than `start` that is not in
+    /// the set
+    ///
+    /// The result is [`None`] when all the values from `start` onwards are in
+    /// the set.
+    pub fn first_missing_from(&self, start: &Idx) -> Option<Idx> {
+        match self.search(start) {
+            Ok(index) => Idx::forward_checked(self.intervals[index].hi()),
+            Err(_) => Some(start.clone())
+        }
+    }
+
+    /// Returns the largest element of the set that is less or equal than
+    /// `value`
+    pub fn floor(&self, value: &Idx) -> Option<Idx> {
+        match self.search(value) {
+            Ok(_) => Some(value.clone()),
+            Err(index) => index.checked_sub(1).map(|i| self.intervals[i].hi().clone())
+        }
+    }
+
+    /// Returns the smallest element of the set that is greater or equal than
+    /// `value`
+    pub fn ceiling(&self, value: &Idx) -> Option<Idx> {
+        match self.search(value) {
+            Ok(_) => Some(value.clone()),
+            Err(index) => self.intervals.get(index).map(|i| i.lo().clone())
+        }
+    }
+
+    /// Returns the number of steps from `value` to the closest element of the
+    /// set, zero if `value` is in the set
+    ///
+    /// The result is [`None`] if the set is empty, the distance saturates at
+    /// [`usize::MAX`].
+    pub fn distance(&self, value: &Idx) -> Option<usize> {
+        self.nearest_with_distance(value).map(|(_, distance)| distance)
+    }
+
+    /// Returns the element of the set closest to `value`
+    ///
+    /// When `value` is halfway between two elements the smaller one is
+    /// returned, the result is [`None`] if the set is empty.
+    pub fn nearest(&self, value: &Idx) -> Option<Idx> {
+        self.nearest_with_distance(value).map(|(nearest, _)| nearest)
+    }
+
+    /// Removes and returns the first interval of the set, or [`None`] if it is
+    /// empty
+    ///
+    /// The remaining intervals are shifted, so this takes linear time in the
+    /// number of intervals, unlike [`IntervalSet::pop_last`]. To consume the
+    /// whole set in ascending order use [`IntervalSet::drain`] instead.
+    pub fn pop_first(&mut self) -> Option<Interval<Idx>> {
+        if self.intervals.is_empty() {
+            None
+        } else {
+            Some(self.intervals.remove(0))
+        }
+    }
+
+    /// Removes and returns the last interval of the set, or [`None`] if it is
+    /// empty
+    ///
+    /// This takes constant time.
+    pub fn pop_last(&mut self) -> Option<Interval<Idx>> {
+        self.intervals.pop()
+    }
+
+    /// Checks if `value` is an element of the set
+    pub fn contains(&self, value: &Idx) -> bool {
+        self.search(value).is_ok()
+    }
+
+    /// Checks which of the `points` are elements of the set
+    ///
+    /// The points must be in ascending order, this allows to answer all the
+    /// queries with a single pass over the intervals. When the points are not
+    /// sorted some elements of the set may be reported as missing.
+    pub fn contains_sorted<'a>(&'a self, points: impl Iterator<Item = &'a Idx>) -> impl Iterator<Item = bool> {
+        let mut rest = self.intervals.as_slice();
+        points.map(move |point| {
+            while let [first, tail @ ..] = rest && first.hi() < point {
+                rest = tail;
+            }
+            rest.first().is_some_and(|interval| interval.lo() <= point)
+        })
+    }
+
+    /// Returns the interval of the set that contains `value`
+    pub fn interval_containing(&self, value: &Idx) -> Option<&Interval<Idx>> {
+        self.search(value).ok().map(|index| &self.intervals[index])
+    }
+
+    /// Checks if all the elements of `interval` are in the set
+    pub fn contains_interval(&self, interval: impl Into<Interval<Idx>>) -> bool {
+        let interval = interval.into();
+        match self.search(interval.lo()) {
+            Ok(index) => interval.hi() <= self.intervals[index].hi(),
+            Err(_) => false
+        }
+    }
+
+    /// Checks if all the given values or intervals are in the set
+    ///
+    /// Returns `true` for an empty iterator, and stops at the first item
+    /// that is not contained.
+    pub fn contains_all<I>(&self, items: I) -> bool
+        where I: IntoIterator, I::Item: Into<Interval<Idx>>
+    {
+        items.into_iter().all(|item| self.contains_interval(item))
+    }
+
+    /// Checks if any element of the given values or intervals is in the set
+    ///
+    /// Returns `false` for an empty iterator, and stops at the first item
+    /// that overlaps the set.
+    pub fn contains_any<I>(&self, items: I) -> bool
+        where I: IntoIterator, I::Item: Into<Interval<Idx>>
+    {
+        items.into_iter().any(|item| !self.overlapping_span(&item.into()).is_empty())
+    }
+
     /// Inserts an interval in the set
-    pub fn insert(&mut self, interval: impl Into<Interval<Idx>>) {
-        // TODO: make this better
-        let tmp = Self::interval(interval);
-        *self = self.union(&tmp);
+    ///
+    /// Returns an [`InsertOutcome`] that describes how the set changed, use
+    /// [`IntervalSet::probe_insert`] to get it without modifying the set.
+    pub fn insert(&mut self, interval: impl Into<Interval<Idx>>) -> InsertOutcome<Idx> {
+        let (span, outcome) = self.probe_span(interval.into());
+        storage::splice(&mut self.intervals, span, [outcome.interval.clone()]);
+        outcome
+    }
+
+    /// Inserts the elements of an half-open range in the set
+    ///
+    /// Empty ranges, like `5..5`, are ignored and return [`None`], otherwise
+    /// this is the same as [`IntervalSet::insert`].
+    pub fn insert_range(&mut self, range: Range<Idx>) -> Option<InsertOutcome<Idx>> {
+        let interval = Interval::try_from_range(range).ok()?;
+        Some(self.insert(interval))
+    }
+
+    /// Removes the elements of `interval` from the set
+    ///
+    /// Intervals partially covered by the removed interval are trimmed or split.
+    /// The affected intervals are found with a binary search and replaced in
+    /// place, the rest of the set is not copied.
+    pub fn remove(&mut self, interval: impl Into<Interval<Idx>>) {
+        let interval = interval.into();
+        let span = self.overlapping_span(&interval);
+        if span.is_empty() { return; }
+
+        let (left, _) = self.intervals[span.start].difference(&interval);
+        let (_, right) = self.intervals[span.end - 1].difference(&interval);
+        storage::splice(&mut self.intervals, span, left.into_iter().chain(right));
+    }
+
+    /// Removes the elements of all the given intervals from the set
+    ///
+    /// The intervals are sorted and merged first, then they are subtracted in
+    /// place with a single pass over the set.
+    pub fn remove_all<I>(&mut self, intervals: I)
+        where I: IntoIterator, I::Item: Into<Interval<Idx>>
+    {
+        let removed: Self = intervals.into_iter().collect();
+        *self -= &removed;
+    }
+
+    /// Removes `value` from the set, returning whether it was in the set
+    ///
+    /// The interval containing the value is split in at most two intervals.
+    pub fn remove_point(&mut self, value: &Idx) -> bool {
+        let Ok(index) = self.search(value) else { return false };
+
+        let (left, right) = self.intervals[index].difference(&value.into());
+        storage::splice(&mut self.intervals, index..index + 1, left.into_iter().chain(right));
+        true
+    }
+
+    /// Inserts `value` if it is not in the set, otherwise removes it
+    pub fn toggle(&mut self, value: Idx) {
+        if !self.remove_point(&value) {
+            self.insert(value);
+        }
+    }
+
+    /// Retains only the intervals for which `f` returns `true`
+    pub fn retain(&mut self, mut f: impl FnMut(&Interval<Idx>) -> bool) {
+        self.intervals.retain_mut(|interval| f(interval));
+    }
+
+    /// Splits the set in two at `at`
+    ///
+    /// The set keeps the elements smaller than `at`, while the returned set
+    /// contains the elements greater or equal than `at`.
+    pub fn split_off(&mut self, at: &Idx) -> Self {
+        let index = self.intervals.partition_point(|i| i.hi() < at);
+        let mut tail = storage::split_off(&mut self.intervals, index);
+        if let Some(first) = tail.first_mut() && first.lo() < at {
+            self.intervals.push(Interval::new(first.lo().clone(), Idx::backward(at)));
+            *first = Interval::new(at.clone(), first.hi().clone());
+        }
+        Self { intervals: tail }
+    }
+
+    /// Removes all the elements of the set outside of `window`
+    pub fn restrict(&mut self, window: impl Into<Interval<Idx>>) {
+        let window = window.into();
+        let span = self.overlapping_span(&window);
+        self.intervals.truncate(span.end);
+        self.intervals.drain(..span.start);
+
+        if let Some(first) = self.intervals.first_mut() && first.lo() < window.lo() {
+            *first = Interval::new(window.lo().clone(), first.hi().clone());
+        }
+        if let Some(last) = self.intervals.last_mut() && last.hi() > window.hi() {
+            *last = Interval::new(last.lo().clone(), window.hi().clone());
+        }
+    }
+
+    /// Previews how inserting `interval` would change the set, without
+    /// modifying it
+    ///
+    /// The result reports if the interval is already covered by the set, how
+    /// many of the existing intervals would be merged and the resulting merged
+    /// interval.
+    pub fn probe_insert(&self, interval: impl Into<Interval<Idx>>) -> InsertOutcome<Idx> {
+        self.probe_span(interval.into()).1
+    }
+
+    /// Moves all the intervals of `other` at the end of the set
+    ///
+    /// All the elements of `other` must be greater than the elements of the
+    /// set, so no merge pass is needed: the intervals at the seam are merged
+    /// if they touch.
+    ///
+    /// Panics:
+    /// - If the smallest element of `other` is not greater than the largest
+    ///   element of the set
+    pub fn append(&mut self, other: Self) {
+        if let (Some(last), Some(first)) = (self.last(), other.first()) {
+            assert!(last < first, "The appended set must be greater than the set");
+        }
+
+        let mut intervals = other.intervals.into_iter();
+        if let Some(first) = intervals.next() {
+            self.push(first);
+        }
+        self.intervals.extend(intervals);
+    }
+
+    /// Inserts intervals sorted by lower bound in a single linear pass
+    ///
+    /// Each interval is merged with the last interval of the set when they
+    /// overlap or touch, no other interval of the set is visited.
+    ///
+    /// Panics:
+    /// - If an interval starts before the last interval of the set, this
+    ///   happens when the intervals are not sorted by lower bound
+    pub fn extend_from_sorted<I>(&mut self, intervals: I)
+        where I: IntoIterator, I::Item: Into<Interval<Idx>>
+    {
+        for interval in intervals {
+            let interval = interval.into();
+            if let Some(last) = self.intervals.last() {
+                assert!(last.lo() <= interval.lo(), "The intervals must be sorted by lower bound");
+            }
+            self.push(interval);
+        }
+    }
+
+    /// Opens a gap of `len` elements at `at`, shifting all the elements greater
+    /// or equal than `at` up by `len`
+    ///
+    /// An interval containing `at` is split in two.
+    ///
+    /// Panics:
+    /// - If shifting an element overflows the index type, the set is checked
+    ///   before any change so it is left untouched
+    pub fn insert_gap(&mut self, at: &Idx, len: usize) {
+        if len == 0 { return; }
+        // Only the largest element can overflow, check it before mutating the
+        // set so that a panic doesn't leave it unsorted
+        if let Some(last) = self.intervals.last() && last.hi() >= at {
+            assert!(
+                Idx::forward_by_checked(last.hi(), len).is_some(),
+                "overflow in `IntervalSet::insert_gap`"
+            );
+        }
+
+        let mut start = self.intervals.partition_point(|i| i.hi() < at);
+        if let Some(interval) = self.intervals.get(start) && interval.lo() < at {
+            let left = Interval::new(interval.lo().clone(), Idx::backward(at));
+            let right = Interval::new(at.clone(), interval.hi().clone());
+            self.intervals[start] = right;
+            self.intervals.insert(start, left);
+            start += 1;
+        }
+
+        for interval in &mut self.intervals[start..] {
+            let lo = Idx::forward_by(interval.lo(), len);
+            let hi = Idx::forward_by(interval.hi(), len);
+            *interval = Interval::new(lo, hi);
+        }
+    }
+
+    /// Deletes the `len` elements starting at `at`, shifting all the elements
+    /// after the deleted span down by `len`
+    ///
+    /// Intervals that end up touching after the deletion are merged.
+    pub fn delete_span(&mut self, at: &Idx, len: usize) {
+        if len == 0 { return; }
+
+        let start = self.intervals.partition_point(|i| i.hi() < at);
+        let Some(last) = self.intervals[start..].last() else { return };
+        // When the span overflows it covers all the elements from `at` onwards
+        let end = Idx::forward_by_checked(at, len - 1)
+            .unwrap_or_else(|| last.hi().clone());
+        let span = Interval::new(at.clone(), end);
+
+        for interval in storage::split_off(&mut self.intervals, start) {
+            let (below, above) = interval.difference(&span);
+            if let Some(below) = below { self.push(below); }
+            if let Some(above) = above {
+                let lo = Idx::backward_by(above.lo(), len);
+                let hi = Idx::backward_by(above.hi(), len);
+                self.push(Interval::new(lo, hi));
+            }
+        }
     }
 
     /// Performs the union between two sets
     pub fn union(&self, other: &Self) -> Self {
-        let mut result = vec![];
+        let mut result = Self::empty();
 
-        let mut iter = MergeIter::new(
+        let iter = MergeIter::new(
             self.iter(), other.iter(),
             |l, r| l.lo().cmp(r.lo())
         );
-
-        let mut prev = match iter.next() {
-            Some(p) => p.clone(),
-            None => return Self { intervals: result }
-        };
         for interval in iter {
-            if interval.lo() <= &Idx::forward(prev.hi()) {
-                prev = prev.hull(interval);
-            } else {
-                result.push(prev);
-                prev = interval.clone();
-            }
+            result.push(interval.clone());
         }
-        result.push(prev);
 
-        Self { intervals: result }
+        result
+    }
+
+    /// Performs the union of any number of sets
+    ///
+    /// All the intervals are collected in a single buffer and merged once,
+    /// instead of allocating a new set for every pairwise union.
+    pub fn union_all<S>(sets: impl IntoIterator<Item = S>) -> Self
+        where S: Borrow<Self>
+    {
+        let mut intervals = vec![];
+        for set in sets {
+            intervals.extend_from_slice(&set.borrow().intervals);
+        }
+        Self::from(intervals)
     }
 
     /// Performs the intersection between two sets
     pub fn intersection(&self, other: &Self) -> Self {
-        let mut result = vec![];
+        let intersections = Intersections { lhs: &self.intervals, rhs: &other.intervals };
+        Self { intervals: intersections.collect() }
+    }
 
-        let mut iter = MergeIter::new(
-            self.iter(), other.iter(),
-            |l, r| l.lo().cmp(r.lo())
-        );
+    /// Computes the symmetric difference between the two sets
+    ///
+    /// The result is the set containing the elements that are in only one of
+    /// the two sets
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.difference(other).union(&other.difference(self))
+    }
 
-        let mut prev = match iter.next() {
-            Some(p) => p.clone(),
-            None => return Self { intervals: result }
-        };
-        for interval in iter {
-            if interval.lo() <= prev.hi() {
-                if let Some(intersection) = prev.intersection(interval) {
-                    result.push(intersection);
-                }
+    /// Returns the largest interval contained in both sets
+    ///
+    /// When multiple intervals have the same size the first one is returned,
+    /// the result is [`None`] if the sets are disjoint.
+    pub fn longest_common_run(&self, other: &Self) -> Option<Interval<Idx>> {
+        let intersections = Intersections { lhs: &self.intervals, rhs: &other.intervals };
+        intersections.reduce(|longest, interval| {
+            if interval.size() > longest.size() { interval } else { longest }
+        })
+    }
 
-                if interval.hi() > prev.hi() {
-                    prev = interval.clone();
-                }
-            } else {
-                prev = interval.clone();
-            }
-        }
+    /// Computes the Jaccard index between the two sets, the size of the
+    /// intersection divided by the size of the union
+    ///
+    /// The result is [`None`] when both sets are empty. The sizes are
+    /// computed with [`IntervalSet::size`] so they saturate at `usize::MAX`.
+    pub fn jaccard(&self, other: &Self) -> Option<f64> {
+        let common = self.intersection_size(other) as f64;
+        let union = self.size() as f64 + other.size() as f64 - common;
+        (union > 0.0).then(|| common / union)
+    }
 
-        Self { intervals: result }
+    /// Computes the overlap coefficient between the two sets, the size of the
+    /// intersection divided by the size of the smaller set
+    ///
+    /// The result is [`None`] when one of the sets is empty. The sizes are
+    /// computed with [`IntervalSet::size`] so they saturate at `usize::MAX`.
+    pub fn overlap_coefficient(&self, other: &Self) -> Option<f64> {
+        let smaller = self.size().min(other.size());
+        (smaller > 0).then(|| self.intersection_size(other) as f64 / smaller as f64)
     }
 
     /// Computes the difference between the two sets
@@ -146,7 +650,7 @@ impl<Idx: Step> IntervalSet<Idx> {
     /// The result is the set containing all elements in `self` but not in
     /// `other`
     pub fn difference(&self, other: &Self) -> Self {
-        let mut result = vec![];
+        let mut result = Intervals::new();
 
         let mut a_iter = self.iter();
         let mut b_iter = other.iter();
@@ -173,31 +677,567 @@ impl<Idx: Step> IntervalSet<Idx> {
         Self { intervals: result }
     }
 
+    /// Removes the elements of `other` from the set, without allocating a new
+    /// set
+    ///
+    /// Same as `-=`, the result is computed in place with a single pass over
+    /// both sets.
+    pub fn difference_in_place(&mut self, other: &Self) {
+        *self -= other;
+    }
+
+    /// Checks if all the elements of the set are in `other`
+    pub fn is_subset(&self, other: &Self) -> bool {
+        let mut others = other.iter();
+        let mut current = others.next();
+        for interval in self.iter() {
+            while let Some(o) = current && o.hi() < interval.lo() {
+                current = others.next();
+            }
+            match current {
+                Some(o) if o.lo() <= interval.lo() && interval.hi() <= o.hi() => {}
+                _ => return false
+            }
+        }
+        true
+    }
+
+    /// Checks if all the elements of `other` are in the set
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Checks if the sets have no elements in common
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        let mut intersections = Intersections { lhs: &self.intervals, rhs: &other.intervals };
+        intersections.next().is_none()
+    }
+
+    /// Classifies the relation between two sets
+    ///
+    /// The sets are visited once, stopping as soon as the result is known.
+    /// Subset relations take precedence over disjointness, so the empty set is
+    /// a [`SetRelation::ProperSubset`] of any non empty set.
+    pub fn relation(&self, other: &Self) -> SetRelation {
+        let (mut only_a, mut only_b, mut common) = (false, false, false);
+
+        let mut a_iter = self.iter().peekable();
+        let mut b_iter = other.iter().peekable();
+        while let (Some(a), Some(b)) = (a_iter.peek(), b_iter.peek()) {
+            if a.overlaps(b) {
+                // Since the intervals are not touching, elements just outside
+                // an interval are never in the set
+                common = true;
+                only_a |= a.lo() < b.lo();
+                only_b |= b.lo() < a.lo();
+                match a.hi().cmp(b.hi()) {
+                    Ordering::Less => { only_b = true; a_iter.next(); }
+                    Ordering::Greater => { only_a = true; b_iter.next(); }
+                    Ordering::Equal => { a_iter.next(); b_iter.next(); }
+                }
+            } else if a.hi() < b.lo() {
+                only_a = true;
+                a_iter.next();
+            } else {
+                only_b = true;
+                b_iter.next();
+            }
+
+            if only_a && only_b && common {
+                return SetRelation::Overlapping;
+            }
+        }
+        only_a |= a_iter.peek().is_some();
+        only_b |= b_iter.peek().is_some();
+
+        match (only_a, only_b) {
+            (false, false) => SetRelation::Equal,
+            (false, true) => SetRelation::ProperSubset,
+            (true, false) => SetRelation::ProperSuperset,
+            (true, true) if !common => SetRelation::Disjoint,
+            (true, true) => SetRelation::Overlapping,
+        }
+    }
+
+    /// Splits the set into clusters separated by gaps of at least `min_gap`
+    /// elements
+    ///
+    /// The clusters are returned in order, the empty set has no clusters.
+    pub fn split_by_gaps(&self, min_gap: usize) -> Vec<Self> {
+        let mut clusters: Vec<Self> = vec![];
+        let mut prev: Option<&Interval<Idx>> = None;
+        for interval in self.iter() {
+            let split = prev.is_none_or(|prev| {
+                let gap = match Idx::steps_between(prev.hi(), interval.lo()).1 {
+                    Some(steps) => steps - 1,
+                    None => usize::MAX
+                };
+                gap >= min_gap
+            });
+
+            match clusters.last_mut() {
+                Some(cluster) if !split => cluster.intervals.push(interval.clone()),
+                _ => clusters.push(Self::interval(interval.clone()))
+            }
+            prev = Some(interval);
+        }
+        clusters
+    }
+
+    /// Counts the elements of the set in windows of `window_len` elements that
+    /// slide over `range` moving by `stride` elements
+    ///
+    /// The iterator yields each window together with the number of elements
+    /// of the set it contains, only windows fully contained in `range` are
+    /// produced. The count of each window is computed from the previous one,
+    /// so the scan is linear in the number of windows and intervals.
+    ///
+    /// Panics:
+    /// - If `window_len` or `stride` are zero
+    pub fn window_coverage(
+        &self,
+        window_len: usize,
+        stride: usize,
+        range: impl Into<Interval<Idx>>
+    ) -> impl Iterator<Item = (Interval<Idx>, usize)> {
+        assert!(window_len > 0, "The window length must be greater than zero");
+        assert!(stride > 0, "The stride must be greater than zero");
+
+        let range = range.into();
+        let mut entering = CoverageCursor { intervals: &self.intervals };
+        let mut leaving = CoverageCursor { intervals: &self.intervals };
+        let mut prev: Option<(Interval<Idx>, usize)> = None;
+        let mut next_lo = Some(range.lo().clone());
+
+        std::iter::from_fn(move || {
+            let lo = next_lo.take()?;
+            let hi = Idx::forward_by_checked(&lo, window_len - 1)
+                .filter(|hi| hi <= range.hi())?;
+            next_lo = Idx::forward_by_checked(&lo, stride);
+            let window = Interval::new(lo, hi);
+
+            let coverage = match prev.take() {
+                None => entering.count(&window),
+                Some((prev, coverage)) => {
+                    let left_hi = Idx::backward(window.lo()).min(prev.hi().clone());
+                    let left = Interval::new(prev.lo().clone(), left_hi);
+                    let right_lo = Idx::forward(prev.hi()).max(window.lo().clone());
+                    let right = Interval::new(right_lo, window.hi().clone());
+                    coverage - leaving.count(&left) + entering.count(&right)
+                }
+            };
+
+            prev = Some((window.clone(), coverage));
+            Some((window, coverage))
+        })
+    }
+
+    /// Returns the iterator over the gaps between the intervals of the set
+    ///
+    /// The gaps are the maximal intervals not in the set that lie between its
+    /// first and last element.
+    pub fn gaps(&self) -> Gaps<'_, Idx> {
+        Gaps { windows: self.intervals.windows(2) }
+    }
+
+    /// Returns the maximal gap of the set that contains `value`
+    ///
+    /// The result is [`None`] if `value` is in the set. A gap before the first
+    /// or after the last interval extends indefinitely, and the corresponding
+    /// end of the gap is [`Bound::Unbounded`].
+    pub fn gap_containing(&self, value: &Idx) -> Option<(Bound<Idx>, Bound<Idx>)> {
+        let index = self.search(value).err()?;
+        let lo = match index.checked_sub(1) {
+            Some(prev) => Bound::Included(Idx::forward(self.intervals[prev].hi())),
+            None => Bound::Unbounded
+        };
+        let hi = match self.intervals.get(index) {
+            Some(next) => Bound::Included(Idx::backward(next.lo())),
+            None => Bound::Unbounded
+        };
+        Some((lo, hi))
+    }
+
+    /// Returns the iterator over the segments that tile `window`
+    ///
+    /// Each segment is yielded with a flag that tells if it is covered by the
+    /// set, consecutive segments alternate between covered and uncovered.
+    pub fn segments(&self, window: impl Into<Interval<Idx>>) -> Segments<'_, Idx> {
+        let window = window.into();
+        let start = self.intervals.partition_point(|i| i.hi() < window.lo());
+        Segments {
+            intervals: &self.intervals[start..],
+            next: Some(window.lo().clone()),
+            window
+        }
+    }
+
+    /// Returns the iterator over the intervals of the set as
+    /// [`RangeInclusive`] values
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<Idx>> {
+        self.iter().cloned().map(RangeInclusive::from)
+    }
+
+    /// Returns the iterator over the intervals of the set as `(start, length)`
+    /// runs
+    ///
+    /// Intervals with more than `usize::MAX` elements are split in multiple
+    /// consecutive runs.
+    pub fn to_runs(&self) -> impl Iterator<Item = (Idx, usize)> {
+        self.iter().flat_map(|interval| {
+            let mut next = Some(interval.lo().clone());
+            std::iter::from_fn(move || {
+                let start = next.take()?;
+                let len = Idx::steps_between(&start, interval.hi()).1
+                    .and_then(|steps| steps.checked_add(1));
+                match len {
+                    Some(len) => Some((start, len)),
+                    None => {
+                        next = Idx::forward_by_checked(&start, usize::MAX);
+                        Some((start, usize::MAX))
+                    }
+                }
+            })
+        })
+    }
+
+    /// Takes the complement of the set within `universe`, returning the set
+    /// that contains the elements of `universe` not in the current set
+    ///
+    /// Unlike [`IntervalSet::complement`] this does not require the index to
+    /// be [`BoundedFns`].
+    pub fn complement_within(&self, universe: impl Into<Interval<Idx>>) -> Self {
+        Self::interval(universe).difference(self)
+    }
+
+    /// Returns the `index`-th interval of the set, or [`None`] if the index is
+    /// out of bounds
+    pub fn get(&self, index: usize) -> Option<&Interval<Idx>> {
+        self.intervals.get(index)
+    }
+
+    /// Returns the intervals of the set as a slice
+    ///
+    /// The intervals are sorted and don't overlap or touch.
+    pub fn as_slice(&self) -> &[Interval<Idx>] {
+        &self.intervals
+    }
+
     /// Returns the iterator over all the intervals in the set
     pub fn iter(&self) -> std::slice::Iter<'_, Interval<Idx>> {
         self.intervals.iter()
     }
+
+    /// Returns the iterator over all the elements in the set, in ascending
+    /// order
+    ///
+    /// The iterator is double ended, use [`Iterator::rev`] to get the elements
+    /// in descending order.
+    pub fn elements(&self) -> Elements<'_, Idx> {
+        Elements { intervals: self.intervals.iter(), front: None, back: None }
+    }
+
+    /// Returns the iterator over all the elements in the set, in ascending
+    /// order, consuming the set
+    ///
+    /// Like [`IntervalSet::elements`] the iterator is double ended.
+    pub fn into_elements(self) -> IntoElements<Idx> {
+        IntoElements { intervals: storage::into_vec(self.intervals).into_iter(), front: None, back: None }
+    }
+
+    /// Removes all the intervals from the set, returning them in an iterator
+    ///
+    /// The set keeps its allocated capacity, so it can be reused.
+    pub fn drain(&mut self) -> impl DoubleEndedIterator<Item = Interval<Idx>> + ExactSizeIterator {
+        self.intervals.drain(..)
+    }
+
+    /// Binary searches the interval containing `value`
+    ///
+    /// Returns `Ok` with the index of the interval that contains the value, or
+    /// `Err` with the index of the first interval after the value.
+    fn search(&self, value: &Idx) -> Result<usize, usize> {
+        self.intervals.binary_search_by(|interval| {
+            if interval.hi() < value {
+                Ordering::Less
+            } else if interval.lo() > value {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+    }
+
+    /// Returns the closest element to `value` and its distance from `value`
+    fn nearest_with_distance(&self, value: &Idx) -> Option<(Idx, usize)> {
+        let index = match self.search(value) {
+            Ok(_) => return Some((value.clone(), 0)),
+            Err(index) => index
+        };
+        let below = index.checked_sub(1).map(|i| {
+            let hi = self.intervals[i].hi();
+            (hi.clone(), Idx::steps_between(hi, value).0)
+        });
+        let above = self.intervals.get(index).map(|interval| {
+            let lo = interval.lo();
+            (lo.clone(), Idx::steps_between(value, lo).0)
+        });
+        match (below, above) {
+            (Some(below), Some(above)) => Some(if above.1 < below.1 { above } else { below }),
+            (below, above) => below.or(above)
+        }
+    }
+
+    /// Returns the number of elements in both sets, without building the
+    /// intersection
+    fn intersection_size(&self, other: &Self) -> usize {
+        let intersections = Intersections { lhs: &self.intervals, rhs: &other.intervals };
+        intersections.fold(0usize, |size, interval| size.saturating_add(interval.size()))
+    }
+
+    /// Combines the set with `other` in place, keeping the elements for which
+    /// `keep(in_self, in_other)` is true
+    ///
+    /// The intervals of both sets are swept in a single linear pass, and the
+    /// result is written over the intervals of the set that were already read.
+    fn combine_in_place(&mut self, other: &Self, keep: impl Fn(bool, bool) -> bool) {
+        let mut out = InPlaceWriter {
+            intervals: &mut self.intervals,
+            read: 0,
+            write: 0,
+            pending: VecDeque::new(),
+            last: None
+        };
+        let mut rhs_iter = other.iter();
+        let mut lhs = out.next();
+        let mut rhs = rhs_iter.next().cloned();
+
+        loop {
+            match (lhs.take(), rhs.take()) {
+                (None, None) => break,
+                (Some(l), None) => {
+                    if keep(true, false) { out.emit(l); }
+                    lhs = out.next();
+                }
+                (None, Some(r)) => {
+                    if keep(false, true) { out.emit(r); }
+                    rhs = rhs_iter.next().cloned();
+                }
+                (Some(l), Some(r)) if l.hi() < r.lo() => {
+                    if keep(true, false) { out.emit(l); }
+                    lhs = out.next();
+                    rhs = Some(r);
+                }
+                (Some(l), Some(r)) if r.hi() < l.lo() => {
+                    if keep(false, true) { out.emit(r); }
+                    lhs = Some(l);
+                    rhs = rhs_iter.next().cloned();
+                }
+                // The intervals overlap, the part before the overlap is
+                // split off and the overlap is handled in the next iteration
+                (Some(l), Some(r)) if l.lo() < r.lo() => {
+                    if keep(true, false) {
+                        out.emit(Interval::new(l.lo().clone(), Idx::backward(r.lo())));
+                    }
+                    lhs = Some(Interval::new(r.lo().clone(), l.hi().clone()));
+                    rhs = Some(r);
+                }
+                (Some(l), Some(r)) if r.lo() < l.lo() => {
+                    if keep(false, true) {
+                        out.emit(Interval::new(r.lo().clone(), Idx::backward(l.lo())));
+                    }
+                    rhs = Some(Interval::new(l.lo().clone(), r.hi().clone()));
+                    lhs = Some(l);
+                }
+                // The intervals start together
+                (Some(l), Some(r)) => {
+                    let end = l.hi().min(r.hi()).clone();
+                    if keep(true, true) {
+                        out.emit(Interval::new(l.lo().clone(), end.clone()));
+                    }
+                    lhs = if l.hi() > &end {
+                        Some(Interval::new(Idx::forward(&end), l.hi().clone()))
+                    } else {
+                        out.next()
+                    };
+                    rhs = if r.hi() > &end {
+                        Some(Interval::new(Idx::forward(&end), r.hi().clone()))
+                    } else {
+                        rhs_iter.next().cloned()
+                    };
+                }
+            }
+        }
+        out.finish();
+    }
+
+    /// Sorts and merges the intervals in place, restoring the minimized
+    /// representation
+    fn normalize(&mut self) {
+        // The sort is stable, so it takes linear time when the intervals are
+        // made of few sorted runs
+        self.intervals.sort_by(|l, r| l.lo().cmp(r.lo()));
+        self.intervals.dedup_by(|next, prev| Self::merge_sorted(prev, next));
+    }
+
+    /// Appends an interval at the end of the set, merging it with the last
+    /// interval when they overlap or touch
+    ///
+    /// The lower bound of `interval` must not be smaller than the lower bound
+    /// of the last interval of the set.
+    fn push(&mut self, interval: Interval<Idx>) {
+        if let Some(last) = self.intervals.last_mut() && Self::merge_sorted(last, &interval) {
+            return;
+        }
+        self.intervals.push(interval);
+    }
+
+    /// Merges `next` into `prev` if they overlap or touch, returning whether
+    /// the intervals were merged
+    ///
+    /// The lower bound of `next` must not be smaller than the lower bound of
+    /// `prev`.
+    fn merge_sorted(prev: &mut Interval<Idx>, next: &Interval<Idx>) -> bool {
+        let merge = Idx::forward_checked(prev.hi())
+            .is_none_or(|after| next.lo() <= &after);
+        if merge {
+            *prev = prev.hull(next);
+        }
+        merge
+    }
+
+    /// Returns the iterator over the intervals in the set that overlap `range`
+    ///
+    /// The intervals are returned whole, they are not clipped to `range`.
+    pub fn overlapping(&self, range: impl Into<Interval<Idx>>) -> std::slice::Iter<'_, Interval<Idx>> {
+        self.intervals[self.overlapping_span(&range.into())].iter()
+    }
+
+    /// Returns the range of indices of the intervals that overlap `interval`
+    fn overlapping_span(&self, interval: &Interval<Idx>) -> Range<usize> {
+        let start = self.intervals.partition_point(|i| i.hi() < interval.lo());
+        let end = start + self.intervals[start..]
+            .partition_point(|i| i.lo() <= interval.hi());
+        start..end
+    }
+
+    /// Returns the range of indices of the intervals that overlap or touch
+    /// `interval`, these are the intervals that would be merged with it.
+    fn touching(&self, interval: &Interval<Idx>) -> Range<usize> {
+        let start = self.intervals.partition_point(|i| {
+            Idx::forward_checked(i.hi()).is_some_and(|next| &next < interval.lo())
+        });
+        let end = start + self.intervals[start..].partition_point(|i| {
+            Idx::backward_checked(i.lo()).is_none_or(|prev| &prev <= interval.hi())
+        });
+        start..end
+    }
+
+    /// Computes the outcome of inserting `interval`, along with the range of
+    /// indices of the intervals that are replaced by the merged interval
+    fn probe_span(&self, interval: Interval<Idx>) -> (Range<usize>, InsertOutcome<Idx>) {
+        let span = self.touching(&interval);
+        let touching = &self.intervals[span.clone()];
+
+        let covered = matches!(touching, [single]
+            if single.lo() <= interval.lo() && interval.hi() <= single.hi());
+        let interval = match (touching.first(), touching.last()) {
+            (Some(first), Some(last)) => interval.hull(first).hull(last),
+            _ => interval
+        };
+
+        (span, InsertOutcome { covered, merged: touching.len(), interval })
+    }
 }
 
 impl<Idx> IntervalSet<Idx>
-    where Idx: Bounded + Step
+    where Idx: BoundedFns + Step
 {
     /// Returns the set containing all possible values of the type
     ///
-    /// This operation requires the the index is [`Bounded`]
+    /// This operation requires the the index is [`BoundedFns`]
     pub fn full() -> Self {
-        Self { intervals: vec![Interval::full()] }
+        Self::interval(Interval::full())
+    }
+
+    /// Returns the smallest value that is not in the set
+    ///
+    /// The result is [`None`] when the set contains all the values of the type.
+    ///
+    /// This operation requires the the index is [`BoundedFns`]
+    pub fn first_missing(&self) -> Option<Idx> {
+        self.first_missing_from(&Idx::min_value())
     }
 
     /// Takes the complement of the set, retuning the set that contains the
     /// elements not in the current set
     ///
-    /// This operation requires the the index is [`Bounded`]
+    /// This operation requires the the index is [`BoundedFns`]
     pub fn complement(&self) -> Self {
-        Self::full().difference(self)
+        self.complement_within(Interval::full())
+    }
+}
+
+impl IntervalSet<char> {
+    /// Builds the set containing the characters of a string
+    pub fn from_str_chars(s: &str) -> Self {
+        s.chars().collect()
     }
+
+    /// Returns the iterator over all the characters in the set, in order
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        self.elements()
+    }
+
+    /// Returns the string made of all the characters in the set, in order
+    pub fn to_string_of_chars(&self) -> String {
+        self.chars().collect()
+    }
+}
+
+macro_rules! impl_subnets {
+    [$($addr:ty => $bits:ty),*] => {$(
+        impl IntervalSet<$addr> {
+            /// Returns the iterator over the aligned subnets with prefix length
+            /// `prefix_len` that contain addresses of the set
+            ///
+            /// Each subnet is yielded with a flag that tells if it is fully
+            /// covered by the set.
+            ///
+            /// Panics:
+            /// - If `prefix_len` is greater than the number of bits of an address
+            pub fn subnets(&self, prefix_len: u8) -> impl Iterator<Item = (Interval<$addr>, bool)> {
+                const BITS: u32 = <$bits>::BITS;
+                assert!(u32::from(prefix_len) <= BITS, "The prefix length must be at most {BITS}");
+
+                let mask = <$bits>::MAX.checked_shl(BITS - u32::from(prefix_len)).unwrap_or(0);
+                let mut intervals = self.intervals.as_slice();
+                let mut next = intervals.first().map(|i| i.lo().to_bits() & mask);
+
+                std::iter::from_fn(move || {
+                    let base = next.take()?;
+                    let last = base | !mask;
+                    let subnet = Interval::new(<$addr>::from_bits(base), <$addr>::from_bits(last));
+                    let full = intervals.first()
+                        .is_some_and(|i| i.lo() <= subnet.lo() && subnet.hi() <= i.hi());
+
+                    while let [first, rest @ ..] = intervals && first.hi() <= subnet.hi() {
+                        intervals = rest;
+                    }
+                    next = match (last.checked_add(1), intervals.first()) {
+                        (Some(after), Some(first)) => Some(after.max(first.lo().to_bits() & mask)),
+                        _ => None
+                    };
+
+                    Some((subnet, full))
+                })
+            }
+        }
+    )*};
 }
 
+impl_subnets![Ipv4Addr => u32, Ipv6Addr => u128];
+
 impl<Idx> Debug for IntervalSet<Idx>
     where Idx: Debug + Step
 {
@@ -222,12 +1262,429 @@ impl<Idx> Display for IntervalSet<Idx>
     }
 }
 
+macro_rules! impl_set_op {
+    [$($op:ident :: $fn:ident => $method:ident, $doc:literal);*] => {$(
+        impl<Idx: Step> $op<&IntervalSet<Idx>> for &IntervalSet<Idx> {
+            type Output = IntervalSet<Idx>;
+
+            #[doc = $doc]
+            fn $fn(self, rhs: &IntervalSet<Idx>) -> IntervalSet<Idx> {
+                self.$method(rhs)
+            }
+        }
+
+        impl<Idx: Step> $op<IntervalSet<Idx>> for &IntervalSet<Idx> {
+            type Output = IntervalSet<Idx>;
+
+            #[doc = $doc]
+            fn $fn(self, rhs: IntervalSet<Idx>) -> IntervalSet<Idx> {
+                self.$method(&rhs)
+            }
+        }
+
+        impl<Idx: Step> $op<&IntervalSet<Idx>> for IntervalSet<Idx> {
+            type Output = IntervalSet<Idx>;
+
+            #[doc = $doc]
+            fn $fn(self, rhs: &IntervalSet<Idx>) -> IntervalSet<Idx> {
+                self.$method(rhs)
+            }
+        }
+
+        impl<Idx: Step> $op<IntervalSet<Idx>> for IntervalSet<Idx> {
+            type Output = IntervalSet<Idx>;
+
+            #[doc = $doc]
+            fn $fn(self, rhs: IntervalSet<Idx>) -> IntervalSet<Idx> {
+                self.$method(&rhs)
+            }
+        }
+    )*};
+}
+
+impl_set_op![
+    BitOr::bitor => union, "Computes the union of the sets";
+    BitAnd::bitand => intersection, "Computes the intersection of the sets";
+    Sub::sub => difference, "Computes the difference of the sets";
+    BitXor::bitxor => symmetric_difference, "Computes the symmetric difference of the sets"
+];
+
+impl<Idx: Step> BitOrAssign<&IntervalSet<Idx>> for IntervalSet<Idx> {
+    /// Inserts the elements of `rhs` in the set
+    fn bitor_assign(&mut self, rhs: &IntervalSet<Idx>) {
+        self.intervals.extend(rhs.iter().cloned());
+        self.normalize();
+    }
+}
+
+impl<Idx: Step> BitAndAssign<&IntervalSet<Idx>> for IntervalSet<Idx> {
+    /// Removes the elements that are not in `rhs` from the set
+    ///
+    /// The result is computed in place with a single pass over both sets.
+    fn bitand_assign(&mut self, rhs: &IntervalSet<Idx>) {
+        self.combine_in_place(rhs, |lhs, rhs| lhs && rhs);
+    }
+}
+
+impl<Idx: Step> SubAssign<&IntervalSet<Idx>> for IntervalSet<Idx> {
+    /// Removes the elements of `rhs` from the set
+    ///
+    /// The result is computed in place with a single pass over both sets.
+    fn sub_assign(&mut self, rhs: &IntervalSet<Idx>) {
+        self.combine_in_place(rhs, |lhs, rhs| lhs && !rhs);
+    }
+}
+
+impl<Idx: Step> BitXorAssign<&IntervalSet<Idx>> for IntervalSet<Idx> {
+    /// Toggles the elements of `rhs` in the set
+    ///
+    /// The result is computed in place with a single pass over both sets.
+    fn bitxor_assign(&mut self, rhs: &IntervalSet<Idx>) {
+        self.combine_in_place(rhs, |lhs, rhs| lhs != rhs);
+    }
+}
+
+macro_rules! impl_set_op_assign {
+    [$($op:ident :: $fn:ident, $doc:literal);*] => {$(
+        impl<Idx: Step> $op<IntervalSet<Idx>> for IntervalSet<Idx> {
+            #[doc = $doc]
+            fn $fn(&mut self, rhs: IntervalSet<Idx>) {
+                self.$fn(&rhs);
+            }
+        }
+    )*};
+}
+
+impl_set_op_assign![
+    BitOrAssign::bitor_assign, "Inserts the elements of `rhs` in the set";
+    BitAndAssign::bitand_assign, "Removes the elements that are not in `rhs` from the set";
+    SubAssign::sub_assign, "Removes the elements of `rhs` from the set";
+    BitXorAssign::bitxor_assign, "Toggles the elements of `rhs` in the set"
+];
+
+impl<Idx> Not for &IntervalSet<Idx>
+    where Idx: BoundedFns + Step
+{
+    type Output = IntervalSet<Idx>;
+
+    /// Computes the complement of the set
+    fn not(self) -> IntervalSet<Idx> {
+        self.complement()
+    }
+}
+
+impl<Idx> Not for IntervalSet<Idx>
+    where Idx: BoundedFns + Step
+{
+    type Output = IntervalSet<Idx>;
+
+    /// Computes the complement of the set
+    fn not(self) -> IntervalSet<Idx> {
+        self.complement()
+    }
+}
+
+impl<Idx: Step> Default for IntervalSet<Idx> {
+    /// Returns the empty interval set
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
 impl<Idx: Step> IntoIterator for IntervalSet<Idx> {
     type Item = Interval<Idx>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.intervals.into_iter()
+        storage::into_vec(self.intervals).into_iter()
+    }
+}
+
+/// Prefix sums of the interval sizes of a set, for fast rank and select
+/// queries
+///
+/// This struct is created by [`IntervalSet::rank_index`]
+pub struct RankIndex<'a, Idx: Step> {
+    intervals: &'a [Interval<Idx>],
+    /// Number of elements before each interval, saturating at `usize::MAX`
+    before: Vec<usize>
+}
+
+impl<Idx: Step> RankIndex<'_, Idx> {
+    /// Returns the number of elements of the set that are smaller than `value`
+    ///
+    /// Same as [`IntervalSet::rank`], in logarithmic time.
+    pub fn rank(&self, value: &Idx) -> usize {
+        let index = self.intervals.partition_point(|i| i.hi() < value);
+        let Some(interval) = self.intervals.get(index) else {
+            return self.intervals.last()
+                .map_or(0, |last| self.before[index - 1].saturating_add(last.size()));
+        };
+        let partial = if interval.lo() < value {
+            Idx::steps_between(interval.lo(), value).0
+        } else {
+            0
+        };
+        self.before[index].saturating_add(partial)
+    }
+
+    /// Returns the `n`-th smallest element of the set, counting from zero
+    ///
+    /// Same as [`IntervalSet::nth`], in logarithmic time.
+    pub fn nth(&self, n: usize) -> Option<Idx> {
+        let index = self.before.partition_point(|&before| before <= n).checked_sub(1)?;
+        let interval = &self.intervals[index];
+        let offset = n - self.before[index];
+        match interval.size_exact() {
+            Some(size) if offset >= size => None,
+            _ => Idx::forward_by_checked(interval.lo(), offset)
+        }
+    }
+}
+
+/// Iterator over the gaps between the intervals of a set
+///
+/// This struct is created by [`IntervalSet::gaps`]
+pub struct Gaps<'a, Idx: Step> {
+    windows: std::slice::Windows<'a, Interval<Idx>>
+}
+
+impl<Idx: Step> Iterator for Gaps<'_, Idx> {
+    type Item = Interval<Idx>;
+
+    fn next(&mut self) -> Option<Interval<Idx>> {
+        let [prev, next] = self.windows.next()? else { unreachable!() };
+        Some(Interval::new(Idx::forward(prev.hi()), Idx::backward(next.lo())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.windows.size_hint()
+    }
+}
+
+/// Iterator over the elements of a set
+///
+/// This struct is created by [`IntervalSet::elements`]
+pub struct Elements<'a, Idx: Step> {
+    intervals: std::slice::Iter<'a, Interval<Idx>>,
+    front: Option<IntervalIter<Idx>>,
+    back: Option<IntervalIter<Idx>>
+}
+
+impl<Idx: Step> Iterator for Elements<'_, Idx> {
+    type Item = Idx;
+
+    fn next(&mut self) -> Option<Idx> {
+        loop {
+            if let Some(value) = self.front.as_mut().and_then(Iterator::next) {
+                return Some(value);
+            }
+            match self.intervals.next() {
+                Some(interval) => self.front = Some(interval.iter()),
+                None => return self.back.as_mut()?.next()
+            }
+        }
+    }
+}
+
+impl<Idx: Step> DoubleEndedIterator for Elements<'_, Idx> {
+    fn next_back(&mut self) -> Option<Idx> {
+        loop {
+            if let Some(value) = self.back.as_mut().and_then(DoubleEndedIterator::next_back) {
+                return Some(value);
+            }
+            match self.intervals.next_back() {
+                Some(interval) => self.back = Some(interval.iter()),
+                None => return self.front.as_mut()?.next_back()
+            }
+        }
+    }
+}
+
+/// Owning iterator over the elements of a set
+///
+/// This struct is created by [`IntervalSet::into_elements`]
+pub struct IntoElements<Idx: Step> {
+    intervals: std::vec::IntoIter<Interval<Idx>>,
+    front: Option<IntervalIter<Idx>>,
+    back: Option<IntervalIter<Idx>>
+}
+
+impl<Idx: Step> Iterator for IntoElements<Idx> {
+    type Item = Idx;
+
+    fn next(&mut self) -> Option<Idx> {
+        loop {
+            if let Some(value) = self.front.as_mut().and_then(Iterator::next) {
+                return Some(value);
+            }
+            match self.intervals.next() {
+                Some(interval) => self.front = Some(interval.into_iter()),
+                None => return self.back.as_mut()?.next()
+            }
+        }
+    }
+}
+
+impl<Idx: Step> DoubleEndedIterator for IntoElements<Idx> {
+    fn next_back(&mut self) -> Option<Idx> {
+        loop {
+            if let Some(value) = self.back.as_mut().and_then(DoubleEndedIterator::next_back) {
+                return Some(value);
+            }
+            match self.intervals.next_back() {
+                Some(interval) => self.back = Some(interval.into_iter()),
+                None => return self.front.as_mut()?.next_back()
+            }
+        }
+    }
+}
+
+/// Iterator over the covered and uncovered segments of a window
+///
+/// This struct is created by [`IntervalSet::segments`]
+pub struct Segments<'a, Idx: Step> {
+    intervals: &'a [Interval<Idx>],
+    next: Option<Idx>,
+    window: Interval<Idx>
+}
+
+impl<Idx: Step> Iterator for Segments<'_, Idx> {
+    type Item = (Interval<Idx>, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lo = self.next.take()?;
+        let (hi, covered) = match self.intervals.split_first() {
+            Some((first, rest)) if first.lo() <= &lo => {
+                self.intervals = rest;
+                (first.hi().min(self.window.hi()).clone(), true)
+            }
+            Some((first, _)) if first.lo() <= self.window.hi() => {
+                (Idx::backward(first.lo()), false)
+            }
+            _ => (self.window.hi().clone(), false)
+        };
+
+        if &hi < self.window.hi() {
+            self.next = Some(Idx::forward(&hi));
+        }
+        Some((Interval::new(lo, hi), covered))
+    }
+}
+
+/// Cursor that rewrites the intervals of a set while they are being read
+///
+/// The written intervals go over the ones already read, the ones that would
+/// overwrite intervals not read yet are buffered in `pending`.
+struct InPlaceWriter<'a, Idx: Step> {
+    intervals: &'a mut Intervals<Interval<Idx>>,
+    read: usize,
+    write: usize,
+    pending: VecDeque<Interval<Idx>>,
+    /// Last emitted interval, kept to be merged with the next one
+    last: Option<Interval<Idx>>
+}
+
+impl<Idx: Step> InPlaceWriter<'_, Idx> {
+    /// Reads the next interval of the set
+    fn next(&mut self) -> Option<Interval<Idx>> {
+        let interval = self.intervals.get(self.read)?.clone();
+        self.read += 1;
+        while self.write < self.read && let Some(pending) = self.pending.pop_front() {
+            self.intervals[self.write] = pending;
+            self.write += 1;
+        }
+        Some(interval)
+    }
+
+    /// Emits an interval of the result, the intervals must be emitted in order
+    fn emit(&mut self, interval: Interval<Idx>) {
+        if let Some(last) = &mut self.last && IntervalSet::merge_sorted(last, &interval) {
+            return;
+        }
+        if let Some(last) = self.last.replace(interval) {
+            self.write(last);
+        }
+    }
+
+    fn write(&mut self, interval: Interval<Idx>) {
+        if self.write < self.read {
+            self.intervals[self.write] = interval;
+            self.write += 1;
+        } else {
+            self.pending.push_back(interval);
+        }
+    }
+
+    /// Writes the remaining intervals, the set must have been read entirely
+    fn finish(mut self) {
+        if let Some(last) = self.last.take() {
+            self.write(last);
+        }
+        self.intervals.truncate(self.write);
+        self.intervals.extend(self.pending);
+    }
+}
+
+/// Iterator over the intersections between the intervals of two sets
+struct Intersections<'a, Idx: Step> {
+    lhs: &'a [Interval<Idx>],
+    rhs: &'a [Interval<Idx>]
+}
+
+impl<Idx: Step> Iterator for Intersections<'_, Idx> {
+    type Item = Interval<Idx>;
+
+    fn next(&mut self) -> Option<Interval<Idx>> {
+        loop {
+            let (lhs, rhs) = (self.lhs.first()?, self.rhs.first()?);
+            let intersection = lhs.intersection(rhs);
+            if lhs.hi() < rhs.hi() {
+                self.lhs = &self.lhs[1..];
+            } else {
+                self.rhs = &self.rhs[1..];
+            }
+
+            if intersection.is_some() {
+                return intersection;
+            }
+        }
+    }
+}
+
+impl<Idx: Step> From<Vec<Interval<Idx>>> for IntervalSet<Idx> {
+    /// Builds the set from intervals in any order, sorting and merging them
+    ///
+    /// The allocation of the vector is reused for the set.
+    fn from(intervals: Vec<Interval<Idx>>) -> Self {
+        let mut set = Self { intervals: storage::from_vec(intervals) };
+        set.normalize();
+        set
+    }
+}
+
+impl<Idx, I> FromIterator<I> for IntervalSet<Idx>
+    where Idx: Step, I: Into<Interval<Idx>>
+{
+    /// Builds the set from intervals, ranges or values in any order
+    ///
+    /// The intervals are sorted and merged once, so this is faster than
+    /// inserting them one at a time.
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        Self::from(iter.into_iter().map(Into::into).collect::<Vec<_>>())
+    }
+}
+
+impl<Idx, I> Extend<I> for IntervalSet<Idx>
+    where Idx: Step, I: Into<Interval<Idx>>
+{
+    /// Inserts intervals, ranges or values in any order
+    ///
+    /// The new intervals are buffered together with the intervals of the set
+    /// and then sorted and merged once.
+    fn extend<T: IntoIterator<Item = I>>(&mut self, iter: T) {
+        self.intervals.extend(iter.into_iter().map(Into::into));
+        self.normalize();
     }
 }
 
@@ -275,3 +1732,23 @@ impl<'a, Idx, Lhs, Rhs, F> MergeIter<'a, Idx, Lhs, Rhs, F> where
         }
     }
 }
+
+/// Cursor that counts the elements of a set contained in a sequence of
+/// increasing and non overlapping intervals
+struct CoverageCursor<'a, Idx: Step> {
+    intervals: &'a [Interval<Idx>]
+}
+
+impl<Idx: Step> CoverageCursor<'_, Idx> {
+    fn count(&mut self, range: &Interval<Idx>) -> usize {
+        while let [first, rest @ ..] = self.intervals && first.hi() < range.lo() {
+            self.intervals = rest;
+        }
+
+        self.intervals.iter()
+            .take_while(|i| i.lo() <= range.hi())
+            .filter_map(|i| i.intersection(range))
+            .map(|i| i.size())
+            .sum()
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt::{Debug, Display};
+
+use crate::traits::Step;
+
+/// Adapter that implements [`Step`] for the types implementing the unstable
+/// [`std::iter::Step`]
+///
+/// A blanket implementation would conflict with the implementations of this
+/// crate, so the value needs to be wrapped.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StdStep<T>(pub T);
+
+impl<T> Step for StdStep<T>
+    where T: std::iter::Step + Ord
+{
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        std::iter::Step::steps_between(&start.0, &end.0)
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        std::iter::Step::forward_checked(start.0.clone(), 1).map(Self)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        std::iter::Step::backward_checked(start.0.clone(), 1).map(Self)
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        std::iter::Step::forward_checked(start.0.clone(), count).map(Self)
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        std::iter::Step::backward_checked(start.0.clone(), count).map(Self)
+    }
+}
+
+impl<T: Debug> Debug for StdStep<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Display> Display for StdStep<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
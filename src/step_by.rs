@@ -0,0 +1,79 @@
+use std::fmt::{Debug, Display};
+
+use crate::traits::Step;
+
+/// Adapter whose successor and predecessor move by `N` steps of the wrapped
+/// type
+///
+/// Useful when only the values aligned to a stride are meaningful, like page
+/// addresses with `StepBy<u64, 4096>`. All the values stored together should
+/// be congruent modulo `N`, otherwise the number of steps between them is
+/// rounded down.
+///
+/// [`Bounded`](crate::Bounded) is not implemented, since the bounds of the
+/// wrapped type are usually not a multiple of the stride apart.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StepBy<T, const N: u64>(pub T);
+
+impl<T: Step, const N: u64> StepBy<T, N> {
+    /// The stride as an `usize`, or [`None`] if it doesn't fit
+    ///
+    /// A stride of zero is rejected when the type is instantiated.
+    fn stride() -> Option<usize> {
+        const { assert!(N > 0, "The stride must be greater than zero") };
+        usize::try_from(N).ok()
+    }
+}
+
+impl<T: Step, const N: u64> Step for StepBy<T, N> {
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        let (lower, exact) = T::steps_between(&start.0, &end.0);
+        match Self::stride() {
+            Some(stride) => (lower / stride, exact.map(|steps| steps / stride)),
+            None => (0, exact.map(|_| 0))
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        T::forward_by_checked(&start.0, Self::stride()?).map(Self)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        T::backward_by_checked(&start.0, Self::stride()?).map(Self)
+    }
+
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        match count.checked_mul(Self::stride()?) {
+            Some(steps) => T::forward_by_checked(&start.0, steps).map(Self),
+            None => {
+                // The number of steps overflows `usize`, so it is split in two
+                let half = count / 2;
+                Self::forward_by_checked(&Self::forward_by_checked(start, half)?, count - half)
+            }
+        }
+    }
+
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        match count.checked_mul(Self::stride()?) {
+            Some(steps) => T::backward_by_checked(&start.0, steps).map(Self),
+            None => {
+                let half = count / 2;
+                Self::backward_by_checked(&Self::backward_by_checked(start, half)?, count - half)
+            }
+        }
+    }
+}
+
+impl<T: Debug, const N: u64> Debug for StepBy<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl<T: Display, const N: u64> Display for StepBy<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
@@ -0,0 +1,62 @@
+use std::ops::Range;
+
+/// Vector storing the intervals of a set
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type Intervals<T> = Vec<T>;
+
+/// Vector storing the intervals of a set, up to two intervals are stored
+/// without a heap allocation
+#[cfg(feature = "smallvec")]
+pub(crate) type Intervals<T> = smallvec::SmallVec<[T; 2]>;
+
+/// Replaces the elements in `range` with the ones of `replace_with`
+#[cfg(not(feature = "smallvec"))]
+pub(crate) fn splice<T>(vec: &mut Intervals<T>, range: Range<usize>, replace_with: impl IntoIterator<Item = T>) {
+    vec.splice(range, replace_with);
+}
+
+/// Replaces the elements in `range` with the ones of `replace_with`
+#[cfg(feature = "smallvec")]
+pub(crate) fn splice<T>(vec: &mut Intervals<T>, range: Range<usize>, replace_with: impl IntoIterator<Item = T>) {
+    let start = range.start;
+    vec.drain(range);
+    vec.insert_many(start, replace_with);
+}
+
+/// Splits the vector in two at `at`, returning the elements after `at`
+#[cfg(not(feature = "smallvec"))]
+pub(crate) fn split_off<T>(vec: &mut Intervals<T>, at: usize) -> Intervals<T> {
+    vec.split_off(at)
+}
+
+/// Splits the vector in two at `at`, returning the elements after `at`
+#[cfg(feature = "smallvec")]
+pub(crate) fn split_off<T>(vec: &mut Intervals<T>, at: usize) -> Intervals<T> {
+    vec.drain(at..).collect()
+}
+
+/// Converts a [`Vec`] to the storage, reusing its allocation
+#[cfg(not(feature = "smallvec"))]
+pub(crate) fn from_vec<T>(vec: Vec<T>) -> Intervals<T> {
+    vec
+}
+
+/// Converts a [`Vec`] to the storage, reusing its allocation
+#[cfg(feature = "smallvec")]
+pub(crate) fn from_vec<T>(vec: Vec<T>) -> Intervals<T> {
+    Intervals::from_vec(vec)
+}
+
+/// Converts the storage to a [`Vec`], this allocates when the elements are
+/// stored inline
+#[cfg(not(feature = "smallvec"))]
+pub(crate) fn into_vec<T>(vec: Intervals<T>) -> Vec<T> {
+    vec
+}
+
+/// Converts the storage to a [`Vec`], this allocates when the elements are
+/// stored inline
+#[cfg(feature = "smallvec")]
+pub(crate) fn into_vec<T>(vec: Intervals<T>) -> Vec<T> {
+    vec.into_vec()
+}
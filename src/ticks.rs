@@ -0,0 +1,75 @@
+use std::fmt::Display;
+use std::time::Duration;
+
+use crate::traits::{Bounded, Step};
+
+macro_rules! impl_ticks {
+    [$($name: ident, $unit: literal, $suffix: literal, $from: ident, $as: ident;)*] => {$(
+        #[doc = concat!("A point in time or a duration counted in ", $unit)]
+        ///
+        /// Each step moves by one tick, so sets of time windows can be built
+        /// from [`Duration`]s, like the offsets from a fixed epoch.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(pub u64);
+
+        impl Step for $name {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                u64::steps_between(&start.0, &end.0)
+            }
+
+            #[inline]
+            fn forward_checked(start: &Self) -> Option<Self> {
+                u64::forward_checked(&start.0).map(Self)
+            }
+
+            #[inline]
+            fn backward_checked(start: &Self) -> Option<Self> {
+                u64::backward_checked(&start.0).map(Self)
+            }
+
+            #[inline]
+            fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                u64::forward_by_checked(&start.0, count).map(Self)
+            }
+
+            #[inline]
+            fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                u64::backward_by_checked(&start.0, count).map(Self)
+            }
+        }
+
+        impl Bounded for $name {
+            const MIN: Self = Self(u64::MIN);
+            const MAX: Self = Self(u64::MAX);
+        }
+
+        impl From<Duration> for $name {
+            /// Converts the duration, rounding down to a whole tick
+            ///
+            /// Durations longer than the largest tick saturate to
+            #[doc = concat!("[`", stringify!($name), "::MAX`](Bounded::MAX)")]
+            fn from(value: Duration) -> Self {
+                Self(u64::try_from(value.$as()).unwrap_or(u64::MAX))
+            }
+        }
+
+        impl From<$name> for Duration {
+            fn from(value: $name) -> Self {
+                Duration::$from(value.0)
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}{}", self.0, $suffix)
+            }
+        }
+    )*}
+}
+
+impl_ticks![
+    Nanos, "nanoseconds", "ns", from_nanos, as_nanos;
+    Micros, "microseconds", "us", from_micros, as_micros;
+    Millis, "milliseconds", "ms", from_millis, as_millis;
+];
@@ -1,4 +1,5 @@
 use std::net::{Ipv4Addr, Ipv6Addr};
+use std::num::NonZero;
 
 /// Types that have *successor* and *predecessor* operations.
 ///
@@ -30,6 +31,40 @@ pub trait Step: Clone + Ord + Sized {
         Step::backward_checked(start)
             .expect("underflow in `Step::backward`")
     }
+
+    /// Get the `count`-th successor of `start` and check for overflow
+    ///
+    /// The default implementation calls [`Step::forward_checked`] `count`
+    /// times, implementors should override it when possible.
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let mut current = start.clone();
+        for _ in 0..count {
+            current = Step::forward_checked(&current)?;
+        }
+        Some(current)
+    }
+    /// Get the `count`-th successor of `start` panic if overflow is detected
+    fn forward_by(start: &Self, count: usize) -> Self {
+        Step::forward_by_checked(start, count)
+            .expect("overflow in `Step::forward_by`")
+    }
+
+    /// Get the `count`-th predecessor of `start` and check for underflow
+    ///
+    /// The default implementation calls [`Step::backward_checked`] `count`
+    /// times, implementors should override it when possible.
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let mut current = start.clone();
+        for _ in 0..count {
+            current = Step::backward_checked(&current)?;
+        }
+        Some(current)
+    }
+    /// Get the `count`-th predecessor of `start` panic if underflow is detected
+    fn backward_by(start: &Self, count: usize) -> Self {
+        Step::backward_by_checked(start, count)
+            .expect("underflow in `Step::backward_by`")
+    }
 }
 
 macro_rules! impl_step_common {
@@ -46,6 +81,40 @@ macro_rules! impl_step_common {
     };
 }
 
+macro_rules! impl_step_by_unsigned {
+    () => {
+        #[inline]
+        fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+            start.checked_add(Self::try_from(count).ok()?)
+        }
+
+        #[inline]
+        fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+            start.checked_sub(Self::try_from(count).ok()?)
+        }
+    };
+}
+
+macro_rules! impl_step_by_signed {
+    ($unsigned:ty) => {
+        #[inline]
+        fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+            // `count` can be larger than `Self::MAX`, but it always fits the
+            // unsigned type, so wrapping arithmetic gives the right result
+            let count = <$unsigned>::try_from(count).ok()?;
+            let res = start.wrapping_add(count as Self);
+            (res >= *start).then_some(res)
+        }
+
+        #[inline]
+        fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+            let count = <$unsigned>::try_from(count).ok()?;
+            let res = start.wrapping_sub(count as Self);
+            (res <= *start).then_some(res)
+        }
+    };
+}
+
 macro_rules! impl_step {
     {
         narrower than usize: $( [$u_narrower:ty, $i_narrower:ty] ),+;
@@ -64,6 +133,7 @@ macro_rules! impl_step {
             }
 
             impl_step_common!();
+            impl_step_by_unsigned!();
         }
 
         impl Step for $i_narrower {
@@ -78,6 +148,7 @@ macro_rules! impl_step {
             }
 
             impl_step_common!();
+            impl_step_by_signed!($u_narrower);
         }
     )+
 
@@ -97,6 +168,7 @@ macro_rules! impl_step {
             }
 
             impl_step_common!();
+            impl_step_by_unsigned!();
         }
 
         impl Step for $i_wider {
@@ -119,6 +191,7 @@ macro_rules! impl_step {
             }
 
             impl_step_common!();
+            impl_step_by_signed!($u_wider);
         }
     )+
     };
@@ -185,8 +258,111 @@ impl Step for char {
         let ch = unsafe { char::from_u32_unchecked(res) };
         Some(ch)
     }
+
+    #[inline]
+    fn forward_by_checked(start: &char, count: usize) -> Option<char> {
+        let start = *start as u32;
+        let mut res = start.checked_add(u32::try_from(count).ok()?)?;
+        if start < 0xD800 && 0xD800 <= res {
+            res = res.checked_add(0x800)?;
+        }
+        char::from_u32(res)
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &char, count: usize) -> Option<char> {
+        let start = *start as u32;
+        let mut res = start.checked_sub(u32::try_from(count).ok()?)?;
+        if 0xE000 <= start && res < 0xE000 {
+            res = res.checked_sub(0x800)?;
+        }
+        char::from_u32(res)
+    }
+}
+
+macro_rules! impl_step_nonzero_unsigned {
+    [$($t:ty)*] => {$(
+        impl Step for NonZero<$t> {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                <$t>::steps_between(&start.get(), &end.get())
+            }
+
+            #[inline]
+            fn forward_checked(start: &Self) -> Option<Self> {
+                start.checked_add(1)
+            }
+
+            #[inline]
+            fn backward_checked(start: &Self) -> Option<Self> {
+                Self::new(start.get() - 1)
+            }
+
+            #[inline]
+            fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                <$t>::forward_by_checked(&start.get(), count).and_then(Self::new)
+            }
+
+            #[inline]
+            fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                <$t>::backward_by_checked(&start.get(), count).and_then(Self::new)
+            }
+        }
+    )*};
 }
 
+impl_step_nonzero_unsigned![u8 u16 u32 u64 u128 usize];
+
+// The signed types skip zero, like `char` skips the surrogate range
+macro_rules! impl_step_nonzero_signed {
+    [$($t:ty)*] => {$(
+        impl Step for NonZero<$t> {
+            #[inline]
+            fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+                let (start, end) = (start.get(), end.get());
+                match <$t>::steps_between(&start, &end) {
+                    (steps, Some(exact)) if start < 0 && 0 < end => (steps - 1, Some(exact - 1)),
+                    steps => steps
+                }
+            }
+
+            #[inline]
+            fn forward_checked(start: &Self) -> Option<Self> {
+                let next = start.get().checked_add(1)?;
+                Self::new(if next == 0 { 1 } else { next })
+            }
+
+            #[inline]
+            fn backward_checked(start: &Self) -> Option<Self> {
+                let prev = start.get().checked_sub(1)?;
+                Self::new(if prev == 0 { -1 } else { prev })
+            }
+
+            #[inline]
+            fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                let start = start.get();
+                let mut res = <$t>::forward_by_checked(&start, count)?;
+                if start < 0 && 0 <= res {
+                    res = res.checked_add(1)?;
+                }
+                Self::new(res)
+            }
+
+            #[inline]
+            fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+                let start = start.get();
+                let mut res = <$t>::backward_by_checked(&start, count)?;
+                if 0 < start && res <= 0 {
+                    res = res.checked_sub(1)?;
+                }
+                Self::new(res)
+            }
+        }
+    )*};
+}
+
+impl_step_nonzero_signed![i8 i16 i32 i64 i128 isize];
+
 impl Step for Ipv4Addr {
     #[inline]
     fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
@@ -202,6 +378,16 @@ impl Step for Ipv4Addr {
     fn backward_checked(start: &Self) -> Option<Self> {
         u32::backward_checked(&start.to_bits()).map(Self::from_bits)
     }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u32::forward_by_checked(&start.to_bits(), count).map(Self::from_bits)
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u32::backward_by_checked(&start.to_bits(), count).map(Self::from_bits)
+    }
 }
 
 impl Step for Ipv6Addr {
@@ -219,6 +405,363 @@ impl Step for Ipv6Addr {
     fn backward_checked(start: &Self) -> Option<Self> {
         u128::backward_checked(&start.to_bits()).map(Self::from_bits)
     }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u128::forward_by_checked(&start.to_bits(), count).map(Self::from_bits)
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u128::backward_by_checked(&start.to_bits(), count).map(Self::from_bits)
+    }
+}
+
+// Tuples step lexicographically: the last component is stepped first, and
+// when it overflows it wraps around its bounds and the previous component is
+// stepped.
+impl<A, B> Step for (A, B)
+    where A: Step, B: Bounded + Step
+{
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if start > end {
+            return (0, None);
+        }
+        if start.0 == end.0 {
+            return B::steps_between(&start.1, &end.1);
+        }
+
+        // From `start` to the end of its block, the blocks in between, and
+        // from the start of the last block to `end`
+        let head = B::steps_between(&start.1, &B::MAX);
+        let blocks = A::steps_between(&start.0, &end.0);
+        let block = B::steps_between(&B::MIN, &B::MAX);
+        let tail = B::steps_between(&B::MIN, &end.1);
+
+        let lower = (blocks.0 - 1).saturating_mul(block.0.saturating_add(1))
+            .saturating_add(head.0)
+            .saturating_add(tail.0)
+            .saturating_add(1);
+        let exact = (|| {
+            (blocks.1? - 1).checked_mul(block.1?.checked_add(1)?)?
+                .checked_add(head.1?)?
+                .checked_add(tail.1?)?
+                .checked_add(1)
+        })();
+        (lower, exact)
+    }
+
+    fn forward_checked(start: &Self) -> Option<Self> {
+        match B::forward_checked(&start.1) {
+            Some(b) => Some((start.0.clone(), b)),
+            None => Some((A::forward_checked(&start.0)?, B::MIN))
+        }
+    }
+
+    fn backward_checked(start: &Self) -> Option<Self> {
+        match B::backward_checked(&start.1) {
+            Some(b) => Some((start.0.clone(), b)),
+            None => Some((A::backward_checked(&start.0)?, B::MAX))
+        }
+    }
+
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        if let Some(b) = B::forward_by_checked(&start.1, count) {
+            return Some((start.0.clone(), b));
+        }
+        // Move to the start of the next block, then skip whole blocks
+        let rest = count - B::steps_between(&start.1, &B::MAX).1? - 1;
+        let (blocks, rest) = match B::steps_between(&B::MIN, &B::MAX).1.and_then(|s| s.checked_add(1)) {
+            Some(block) => (rest / block, rest % block),
+            None => (0, rest)
+        };
+        Some((A::forward_by_checked(&start.0, blocks + 1)?, B::forward_by_checked(&B::MIN, rest)?))
+    }
+
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        if let Some(b) = B::backward_by_checked(&start.1, count) {
+            return Some((start.0.clone(), b));
+        }
+        // Move to the end of the previous block, then skip whole blocks
+        let rest = count - B::steps_between(&B::MIN, &start.1).1? - 1;
+        let (blocks, rest) = match B::steps_between(&B::MIN, &B::MAX).1.and_then(|s| s.checked_add(1)) {
+            Some(block) => (rest / block, rest % block),
+            None => (0, rest)
+        };
+        Some((A::backward_by_checked(&start.0, blocks + 1)?, B::backward_by_checked(&B::MAX, rest)?))
+    }
+}
+
+// Three components are stepped like `(A, (B, C))`
+impl<A, B, C> Step for (A, B, C)
+    where A: Step, B: Bounded + Step, C: Bounded + Step
+{
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        <(A, (B, C))>::steps_between(&nest(start), &nest(end))
+    }
+
+    fn forward_checked(start: &Self) -> Option<Self> {
+        <(A, (B, C))>::forward_checked(&nest(start)).map(unnest)
+    }
+
+    fn backward_checked(start: &Self) -> Option<Self> {
+        <(A, (B, C))>::backward_checked(&nest(start)).map(unnest)
+    }
+
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        <(A, (B, C))>::forward_by_checked(&nest(start), count).map(unnest)
+    }
+
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        <(A, (B, C))>::backward_by_checked(&nest(start), count).map(unnest)
+    }
+}
+
+fn nest<A: Clone, B: Clone, C: Clone>((a, b, c): &(A, B, C)) -> (A, (B, C)) {
+    (a.clone(), (b.clone(), c.clone()))
+}
+
+fn unnest<A, B, C>((a, (b, c)): (A, (B, C))) -> (A, B, C) {
+    (a, b, c)
+}
+
+// Byte arrays behave like big-endian unsigned integers, consistently with
+// their lexicographic ordering
+impl<const N: usize> Step for [u8; N] {
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if start > end {
+            return (0, None);
+        }
+        let mut borrow = false;
+        let mut diff = [0u8; N];
+        for i in (0..N).rev() {
+            let (byte, under1) = end[i].overflowing_sub(start[i]);
+            let (byte, under2) = byte.overflowing_sub(u8::from(borrow));
+            diff[i] = byte;
+            borrow = under1 || under2;
+        }
+        let steps = diff.iter().try_fold(0usize, |steps, &byte| {
+            steps.checked_mul(256)?.checked_add(usize::from(byte))
+        });
+        match steps {
+            Some(steps) => (steps, Some(steps)),
+            None => (usize::MAX, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        Self::forward_by_checked(start, 1)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        Self::backward_by_checked(start, 1)
+    }
+
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let mut res = *start;
+        let mut carry = count;
+        for byte in res.iter_mut().rev() {
+            if carry == 0 {
+                break;
+            }
+            let sum = usize::from(*byte) + (carry & 0xFF);
+            *byte = sum as u8;
+            carry = (carry >> 8) + (sum >> 8);
+        }
+        (carry == 0).then_some(res)
+    }
+
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let mut res = *start;
+        let mut borrow = count;
+        for byte in res.iter_mut().rev() {
+            if borrow == 0 {
+                break;
+            }
+            let (diff, under) = byte.overflowing_sub((borrow & 0xFF) as u8);
+            *byte = diff;
+            borrow = (borrow >> 8) + usize::from(under);
+        }
+        (borrow == 0).then_some(res)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl Step for uuid::Uuid {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        u128::steps_between(&start.as_u128(), &end.as_u128())
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        u128::forward_checked(&start.as_u128()).map(Self::from_u128)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        u128::backward_checked(&start.as_u128()).map(Self::from_u128)
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u128::forward_by_checked(&start.as_u128(), count).map(Self::from_u128)
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        u128::backward_by_checked(&start.as_u128(), count).map(Self::from_u128)
+    }
+}
+
+#[cfg(feature = "ethnum")]
+impl Step for ethnum::U256 {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if *start <= *end {
+            if let Ok(steps) = usize::try_from(*end - *start) {
+                (steps, Some(steps))
+            } else {
+                (usize::MAX, None)
+            }
+        } else {
+            (0, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        start.checked_add(Self::ONE)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        start.checked_sub(Self::ONE)
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_add(Self::from(count as u128))
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_sub(Self::from(count as u128))
+    }
+}
+
+#[cfg(feature = "ethnum")]
+impl Step for ethnum::I256 {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if *start <= *end {
+            // The difference always fits the unsigned type
+            let steps = end.as_u256().wrapping_sub(start.as_u256());
+            if let Ok(steps) = usize::try_from(steps) {
+                (steps, Some(steps))
+            } else {
+                (usize::MAX, None)
+            }
+        } else {
+            (0, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        start.checked_add(Self::ONE)
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        start.checked_sub(Self::ONE)
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_add(Self::from(count as u128))
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_sub(Self::from(count as u128))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl Step for chrono::NaiveDate {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if *start <= *end {
+            let days = end.signed_duration_since(*start).num_days();
+            match usize::try_from(days) {
+                Ok(steps) => (steps, Some(steps)),
+                Err(_) => (usize::MAX, None)
+            }
+        } else {
+            (0, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        start.succ_opt()
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        start.pred_opt()
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_add_days(chrono::Days::new(u64::try_from(count).ok()?))
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        start.checked_sub_days(chrono::Days::new(u64::try_from(count).ok()?))
+    }
+}
+
+#[cfg(feature = "time")]
+impl Step for time::Date {
+    #[inline]
+    fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+        if *start <= *end {
+            let days = end.to_julian_day() - start.to_julian_day();
+            match usize::try_from(days) {
+                Ok(steps) => (steps, Some(steps)),
+                Err(_) => (usize::MAX, None)
+            }
+        } else {
+            (0, None)
+        }
+    }
+
+    #[inline]
+    fn forward_checked(start: &Self) -> Option<Self> {
+        start.next_day()
+    }
+
+    #[inline]
+    fn backward_checked(start: &Self) -> Option<Self> {
+        start.previous_day()
+    }
+
+    #[inline]
+    fn forward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let day = start.to_julian_day().checked_add(i32::try_from(count).ok()?)?;
+        Self::from_julian_day(day).ok()
+    }
+
+    #[inline]
+    fn backward_by_checked(start: &Self, count: usize) -> Option<Self> {
+        let day = start.to_julian_day().checked_sub(i32::try_from(count).ok()?)?;
+        Self::from_julian_day(day).ok()
+    }
 }
 
 /// Types that are bounded, that have a minimum and maximum value
@@ -229,6 +772,32 @@ pub trait Bounded: Clone + Ord + Sized {
     const MAX: Self;
 }
 
+/// Types that are bounded, but whose extremes are computed by a function
+///
+/// This is implemented for all the [`Bounded`] types, and allows types whose
+/// minimum and maximum can't be built in a const context to be used with the
+/// operations that need the bounds, like [`IntervalSet::complement`].
+///
+/// [`IntervalSet::complement`]: crate::IntervalSet::complement
+pub trait BoundedFns: Clone + Ord + Sized {
+    /// Returns the minimum value for the type
+    fn min_value() -> Self;
+    /// Returns the maximum value for the type
+    fn max_value() -> Self;
+}
+
+impl<T: Bounded> BoundedFns for T {
+    #[inline]
+    fn min_value() -> Self {
+        T::MIN
+    }
+
+    #[inline]
+    fn max_value() -> Self {
+        T::MAX
+    }
+}
+
 macro_rules! impl_bounded {
     [$($t: ty)*] => {$(
         impl Bounded for $t {
@@ -240,6 +809,45 @@ macro_rules! impl_bounded {
 
 impl_bounded![i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize char];
 
+impl_bounded![
+    NonZero<i8> NonZero<i16> NonZero<i32> NonZero<i64> NonZero<i128> NonZero<isize>
+    NonZero<u8> NonZero<u16> NonZero<u32> NonZero<u64> NonZero<u128> NonZero<usize>
+];
+
+#[cfg(feature = "ethnum")]
+impl_bounded![ethnum::U256 ethnum::I256];
+
+#[cfg(feature = "chrono")]
+impl_bounded![chrono::NaiveDate];
+
+#[cfg(feature = "time")]
+impl_bounded![time::Date];
+
+impl<const N: usize> Bounded for [u8; N] {
+    const MIN: Self = [u8::MIN; N];
+    const MAX: Self = [u8::MAX; N];
+}
+
+impl<A, B> Bounded for (A, B)
+    where A: Bounded, B: Bounded
+{
+    const MIN: Self = (A::MIN, B::MIN);
+    const MAX: Self = (A::MAX, B::MAX);
+}
+
+impl<A, B, C> Bounded for (A, B, C)
+    where A: Bounded, B: Bounded, C: Bounded
+{
+    const MIN: Self = (A::MIN, B::MIN, C::MIN);
+    const MAX: Self = (A::MAX, B::MAX, C::MAX);
+}
+
+#[cfg(feature = "uuid")]
+impl Bounded for uuid::Uuid {
+    const MIN: Self = uuid::Uuid::from_u128(u128::MIN);
+    const MAX: Self = uuid::Uuid::from_u128(u128::MAX);
+}
+
 impl Bounded for Ipv4Addr {
     const MIN: Self = Ipv4Addr::from_bits(u32::MIN);
     const MAX: Self = Ipv4Addr::from_bits(u32::MAX);
@@ -0,0 +1,71 @@
+#![cfg(feature = "derive")]
+
+use interval_set::{Bounded, IntervalSet, Step};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Step, Bounded)]
+struct Port(u16);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Step, Bounded)]
+struct Id {
+    raw: u8
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Step, Bounded)]
+enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+#[test]
+fn newtype_step() {
+    assert_eq!(Port::forward(&Port(80)), Port(81));
+    assert_eq!(Port::backward(&Port(80)), Port(79));
+    assert_eq!(Port::forward_checked(&Port(u16::MAX)), None);
+    assert_eq!(Port::backward_checked(&Port(0)), None);
+    assert_eq!(Port::forward_by_checked(&Port(10), 5), Some(Port(15)));
+    assert_eq!(Port::steps_between(&Port(10), &Port(15)), (5, Some(5)));
+
+    assert_eq!(Id::forward(&Id { raw: 1 }), Id { raw: 2 });
+    assert_eq!(Id::backward(&Id { raw: 1 }), Id { raw: 0 });
+    assert_eq!(Id::forward_checked(&Id { raw: u8::MAX }), None);
+}
+
+#[test]
+fn newtype_bounded() {
+    assert_eq!(Port::MIN, Port(u16::MIN));
+    assert_eq!(Port::MAX, Port(u16::MAX));
+    assert_eq!(Id::MIN, Id { raw: u8::MIN });
+    assert_eq!(Id::MAX, Id { raw: u8::MAX });
+
+    let set = IntervalSet::interval(Port(1)..=Port(u16::MAX));
+    assert_eq!(set.complement(), IntervalSet::interval(Port(0)));
+}
+
+#[test]
+fn enum_step() {
+    assert_eq!(Weekday::forward(&Weekday::Mon), Weekday::Tue);
+    assert_eq!(Weekday::backward(&Weekday::Sun), Weekday::Sat);
+    assert_eq!(Weekday::forward_checked(&Weekday::Sun), None);
+    assert_eq!(Weekday::backward_checked(&Weekday::Mon), None);
+    assert_eq!(Weekday::forward_by_checked(&Weekday::Tue, 3), Some(Weekday::Fri));
+    assert_eq!(Weekday::forward_by_checked(&Weekday::Tue, 6), None);
+    assert_eq!(Weekday::backward_by_checked(&Weekday::Fri, 4), Some(Weekday::Mon));
+    assert_eq!(Weekday::steps_between(&Weekday::Mon, &Weekday::Sun), (6, Some(6)));
+    assert_eq!(Weekday::steps_between(&Weekday::Sun, &Weekday::Mon), (0, None));
+}
+
+#[test]
+fn enum_bounded() {
+    assert_eq!(Weekday::MIN, Weekday::Mon);
+    assert_eq!(Weekday::MAX, Weekday::Sun);
+
+    let mut set = IntervalSet::interval(Weekday::Mon..=Weekday::Wed);
+    set.insert(Weekday::Thu..=Weekday::Fri);
+    assert_eq!(set.intervals(), 1);
+    assert_eq!(set.complement(), IntervalSet::interval(Weekday::Sat..=Weekday::Sun));
+}